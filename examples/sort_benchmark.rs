@@ -0,0 +1,23 @@
+//! Doubling experiment over every sort, run with
+//! `cargo run --release --example sort_benchmark [start length] [doublings] [trials] [seed]`
+//!
+//! Defaults to 1000 elements doubled 9 times, one trial and seed 42.
+use rust_algorithms::sort::benchmark::{algorithms, DoublingExperiment};
+use std::env;
+use std::io;
+
+fn main() -> io::Result<()> {
+    let arguments: Vec<u64> = env::args()
+        .skip(1)
+        .map(|argument| argument.parse().expect("arguments have to be numbers"))
+        .collect();
+    let argument = |index: usize, default: u64| arguments.get(index).copied().unwrap_or(default);
+    let experiment = DoublingExperiment::new(
+        argument(0, 1000) as usize,
+        argument(1, 9) as usize,
+        argument(2, 1) as usize,
+        argument(3, 42),
+    );
+
+    experiment.run_all(&algorithms(), &mut io::stdout().lock())
+}
@@ -0,0 +1,481 @@
+use crate::collection::search_tree;
+use crate::collection::search_tree::{InOrder, SearchNode};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+/// AVL tree: heights of the two subtrees of every node differ by at most one,
+/// so the height stays below `1.44 lg N`. It is kept a bit flatter than
+/// [`crate::collection::red_black_tree::RedBlackTree`] at the cost of more rotations
+/// on updates, which pays off when lookups dominate.
+pub struct AvlTree<K, V>
+where
+    K: Ord,
+{
+    root: Link<K, V>,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+    /// Number of nodes on the longest path down to a leaf, 1 for a leaf
+    height: usize,
+    /// Number of nodes in the subtree rooted here
+    size: usize,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Node<K, V> {
+        Node {
+            key,
+            value,
+            left: None,
+            right: None,
+            height: 1,
+            size: 1,
+        }
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+
+    /// Height of the left subtree minus height of the right one
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+impl<K, V> SearchNode for Node<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl<K, V> Default for AvlTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> AvlTree<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    /// Number of keys in the tree
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        self.root = Some(Self::upsert(self.root.take(), key, value));
+    }
+
+    /// The least key and its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min(self.root.as_deref()).map(Node::entry)
+    }
+
+    /// The greatest key and its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max(self.root.as_deref()).map(Node::entry)
+    }
+
+    /// The greatest key less than or equal to `key` and its value
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The least key greater than or equal to `key` and its value
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// Number of keys less than `key`
+    pub fn rank(&self, key: &K) -> usize {
+        search_tree::rank(self.root.as_deref(), key)
+    }
+
+    /// The key of rank `rank` (exactly `rank` keys are less than it) and its value,
+    /// `None` if there are not that many keys
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        search_tree::select(self.root.as_deref(), rank).map(Node::entry)
+    }
+
+    /// Keys in `range` in ascending order
+    pub fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+    where
+        R: RangeBounds<K>,
+    {
+        self.range(range).map(|(key, _)| key).collect()
+    }
+
+    /// Number of keys in `range`, found by ranks of its bounds without visiting the keys
+    pub fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>,
+    {
+        search_tree::size_in_range(self.root.as_deref(), &range)
+    }
+
+    /// Keys and values in ascending order of the keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Keys in `range` and their values in ascending order of the keys
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Iter {
+            in_order: InOrder::new(self.root.as_deref(), &range),
+        }
+    }
+
+    /// Removes the least key, returns it with its value
+    pub fn delete_min(&mut self) -> Option<(K, V)> {
+        let (root, deleted) = Self::_delete_min(self.root.take()?);
+        self.root = root;
+        Some(deleted)
+    }
+
+    /// Removes the greatest key, returns it with its value
+    pub fn delete_max(&mut self) -> Option<(K, V)> {
+        let (root, deleted) = Self::_delete_max(self.root.take()?);
+        self.root = root;
+        Some(deleted)
+    }
+
+    pub fn delete<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if let Some(root) = self.root.take() {
+            self.root = Self::_delete(root, key);
+        }
+    }
+
+    fn upsert(link: Link<K, V>, key: K, value: V) -> Box<Node<K, V>> {
+        let Some(mut node) = link else {
+            return Box::new(Node::new(key, value));
+        };
+        match key.cmp(&node.key) {
+            Ordering::Less => node.left = Some(Self::upsert(node.left.take(), key, value)),
+            Ordering::Equal => {
+                node.value = value;
+                return node;
+            }
+            Ordering::Greater => node.right = Some(Self::upsert(node.right.take(), key, value)),
+        }
+        Self::balance(node)
+    }
+
+    fn _delete_min(mut node: Box<Node<K, V>>) -> (Link<K, V>, (K, V)) {
+        let Some(left) = node.left.take() else {
+            let Node {
+                key, value, right, ..
+            } = *node;
+            return (right, (key, value));
+        };
+        let (left, deleted) = Self::_delete_min(left);
+        node.left = left;
+        (Some(Self::balance(node)), deleted)
+    }
+
+    fn _delete_max(mut node: Box<Node<K, V>>) -> (Link<K, V>, (K, V)) {
+        let Some(right) = node.right.take() else {
+            let Node {
+                key, value, left, ..
+            } = *node;
+            return (left, (key, value));
+        };
+        let (right, deleted) = Self::_delete_max(right);
+        node.right = right;
+        (Some(Self::balance(node)), deleted)
+    }
+
+    fn _delete<Q>(mut node: Box<Node<K, V>>, key: &Q) -> Link<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match key.cmp(node.key.borrow()) {
+            Ordering::Less => {
+                if let Some(left) = node.left.take() {
+                    node.left = Self::_delete(left, key);
+                }
+            }
+            Ordering::Greater => {
+                if let Some(right) = node.right.take() {
+                    node.right = Self::_delete(right, key);
+                }
+            }
+            Ordering::Equal => match (node.left.take(), node.right.take()) {
+                (left, None) => return left,
+                (None, right) => return right,
+                (left, Some(right)) => {
+                    // the successor takes the place of the deleted key
+                    let (right, (min_key, min_value)) = Self::_delete_min(right);
+                    node.left = left;
+                    node.right = right;
+                    node.key = min_key;
+                    node.value = min_value;
+                }
+            },
+        }
+        Some(Self::balance(node))
+    }
+
+    fn rotate_left(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut right = node.right.take().expect("right subtree is the higher one");
+        node.right = right.left.take();
+        node.update();
+        right.left = Some(node);
+        right.update();
+        right
+    }
+
+    fn rotate_right(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut left = node.left.take().expect("left subtree is the higher one");
+        node.left = left.right.take();
+        node.update();
+        left.right = Some(node);
+        left.update();
+        left
+    }
+
+    /// Subtrees of `node` are balanced and their heights differ by at most two,
+    /// one or two rotations bring the difference back to at most one
+    fn balance(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        node.update();
+        let balance_factor = node.balance_factor();
+        if balance_factor > 1 {
+            if node
+                .left
+                .as_ref()
+                .is_some_and(|left| left.balance_factor() < 0)
+            {
+                node.left = node.left.take().map(Self::rotate_left);
+            }
+            node = Self::rotate_right(node);
+        } else if balance_factor < -1 {
+            if node
+                .right
+                .as_ref()
+                .is_some_and(|right| right.balance_factor() > 0)
+            {
+                node.right = node.right.take().map(Self::rotate_right);
+            }
+            node = Self::rotate_left(node);
+        }
+        node
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    in_order: InOrder<'a, Node<K, V>>,
+}
+
+impl<'a, K, V> IntoIterator for &'a AvlTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.in_order.next().map(Node::entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.in_order.remaining, Some(self.in_order.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.in_order.next_back().map(Node::entry)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> where K: Ord {}
+
+pub struct Keys<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> where K: Ord {}
+
+pub struct Values<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> where K: Ord {}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::avl_tree::{height, AvlTree, Link};
+    use crate::collection::test_utils::assert_stays_balanced;
+    use easy_assert::bool_assertions::BooleanAssert;
+
+    /// Heights and sizes are up to date and differ by at most one between siblings
+    fn is_balanced<K, V>(link: &Link<K, V>) -> bool {
+        let Some(node) = link else {
+            return true;
+        };
+        node.balance_factor().abs() <= 1
+            && node.height == 1 + height(&node.left).max(height(&node.right))
+            && node.size == 1 + super::size(&node.left) + super::size(&node.right)
+            && is_balanced(&node.left)
+            && is_balanced(&node.right)
+    }
+
+    fn assert_balanced<K, V>(tree: &AvlTree<K, V>)
+    where
+        K: Ord,
+    {
+        BooleanAssert::assert_that(is_balanced(&tree.root)).is_true();
+        // AVL trees are at most 1.44 times higher than complete ones
+        let max_height = 1.45 * ((tree.len() + 2) as f64).log2();
+        BooleanAssert::assert_that(height(&tree.root) as f64 <= max_height).is_true();
+    }
+
+    #[test]
+    fn stays_balanced() {
+        assert_stays_balanced(AvlTree::new, assert_balanced);
+    }
+}
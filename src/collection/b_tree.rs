@@ -0,0 +1,847 @@
+use std::borrow::Borrow;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+/// Order of [`BTree::new`]
+const DEFAULT_ORDER: usize = 16;
+
+/// In-memory B-tree: every node holds up to `order - 1` keys in a sorted vector and has
+/// one child more than keys, all leaves are on the same level. Wide nodes make the tree
+/// shallow and keep neighbouring keys next to each other, which makes scans cheap.
+///
+/// Every node except the root has at least `ceil(order / 2) - 1` keys.
+pub struct BTree<K, V> {
+    root: Node<K, V>,
+    order: usize,
+}
+
+struct Node<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    /// Empty for leaves, `children[i]` holds the keys between `keys[i - 1]` and `keys[i]`
+    children: Vec<Node<K, V>>,
+    /// Number of keys in the subtree rooted here
+    size: usize,
+}
+
+enum Insertion<K, V> {
+    Replaced,
+    Added,
+    /// The node overflowed: the middle key goes to the parent, followed by the new right sibling
+    Split(K, V, Node<K, V>),
+}
+
+impl<K, V> Node<K, V>
+where
+    K: Ord,
+{
+    fn new() -> Node<K, V> {
+        Node {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children: Vec::new(),
+            size: 0,
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn entry(&self, index: usize) -> (&K, &V) {
+        (&self.keys[index], &self.values[index])
+    }
+
+    /// `Ok` with the index of `key`, or `Err` with the index of the child which may hold it
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by(|probe| probe.borrow().cmp(key))
+    }
+
+    /// Number of keys in the children before `index` (exclusive)
+    fn size_of_children_before(&self, index: usize) -> usize {
+        self.children
+            .iter()
+            .take(index)
+            .map(|child| child.size)
+            .sum()
+    }
+
+    fn insert(&mut self, key: K, value: V, max_keys: usize) -> Insertion<K, V> {
+        let index = match self.search(&key) {
+            Ok(index) => {
+                self.values[index] = value;
+                return Insertion::Replaced;
+            }
+            Err(index) => index,
+        };
+        if self.is_leaf() {
+            self.keys.insert(index, key);
+            self.values.insert(index, value);
+        } else {
+            match self.children[index].insert(key, value, max_keys) {
+                Insertion::Replaced => return Insertion::Replaced,
+                Insertion::Added => {}
+                Insertion::Split(key, value, right) => {
+                    self.keys.insert(index, key);
+                    self.values.insert(index, value);
+                    self.children.insert(index + 1, right);
+                }
+            }
+        }
+        self.size += 1;
+
+        if self.keys.len() > max_keys {
+            self.split()
+        } else {
+            Insertion::Added
+        }
+    }
+
+    /// Keeps the lower half of the keys, the middle one and the upper half go to the caller
+    fn split(&mut self) -> Insertion<K, V> {
+        let middle = self.keys.len() / 2;
+        let mut right = Node {
+            keys: self.keys.split_off(middle + 1),
+            values: self.values.split_off(middle + 1),
+            children: if self.is_leaf() {
+                Vec::new()
+            } else {
+                self.children.split_off(middle + 1)
+            },
+            size: 0,
+        };
+        right.size = right.keys.len() + right.size_of_children_before(right.children.len());
+        self.size -= right.size + 1;
+        let key = self.keys.pop().expect("an overflowing node has keys");
+        let value = self.values.pop().expect("an overflowing node has values");
+        Insertion::Split(key, value, right)
+    }
+
+    /// Children may be left with too few keys, the parent fixes them with [`Node::fix_child`]
+    fn remove<Q>(&mut self, key: &Q, min_keys: usize) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let removed = match (self.search(key), self.is_leaf()) {
+            (Ok(index), true) => Some((self.keys.remove(index), self.values.remove(index))),
+            (Ok(index), false) => {
+                // the predecessor takes the place of the removed key
+                let (key, value) = self.children[index].remove_max(min_keys);
+                let removed = (
+                    mem::replace(&mut self.keys[index], key),
+                    mem::replace(&mut self.values[index], value),
+                );
+                self.fix_child(index, min_keys);
+                Some(removed)
+            }
+            (Err(_), true) => None,
+            (Err(index), false) => {
+                let removed = self.children[index].remove(key, min_keys);
+                if removed.is_some() {
+                    self.fix_child(index, min_keys);
+                }
+                removed
+            }
+        };
+        if removed.is_some() {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    /// The node is not empty
+    fn remove_min(&mut self, min_keys: usize) -> (K, V) {
+        self.size -= 1;
+        if self.is_leaf() {
+            return (self.keys.remove(0), self.values.remove(0));
+        }
+        let removed = self.children[0].remove_min(min_keys);
+        self.fix_child(0, min_keys);
+        removed
+    }
+
+    /// The node is not empty
+    fn remove_max(&mut self, min_keys: usize) -> (K, V) {
+        self.size -= 1;
+        if self.is_leaf() {
+            let key = self.keys.pop().expect("the node is not empty");
+            let value = self.values.pop().expect("the node is not empty");
+            return (key, value);
+        }
+        let last = self.children.len() - 1;
+        let removed = self.children[last].remove_max(min_keys);
+        self.fix_child(last, min_keys);
+        removed
+    }
+
+    /// Refills a child left with too few keys from a sibling which can spare one,
+    /// or merges it with a sibling
+    fn fix_child(&mut self, index: usize, min_keys: usize) {
+        if self.children[index].keys.len() >= min_keys {
+            return;
+        }
+        if index > 0 && self.children[index - 1].keys.len() > min_keys {
+            self.rotate_right(index - 1);
+        } else if index + 1 < self.children.len() && self.children[index + 1].keys.len() > min_keys
+        {
+            self.rotate_left(index);
+        } else if index > 0 {
+            self.merge(index - 1);
+        } else {
+            self.merge(index);
+        }
+    }
+
+    /// Moves the last key of the child left of `separator` up,
+    /// and the separator down to the front of the child on its right
+    fn rotate_right(&mut self, separator: usize) {
+        let (before, after) = self.children.split_at_mut(separator + 1);
+        let (left, right) = (&mut before[separator], &mut after[0]);
+        let key = left.keys.pop().expect("the left sibling can spare a key");
+        let value = left.values.pop().expect("the left sibling can spare a key");
+        right
+            .keys
+            .insert(0, mem::replace(&mut self.keys[separator], key));
+        right
+            .values
+            .insert(0, mem::replace(&mut self.values[separator], value));
+        let mut moved = 1;
+        if let Some(child) = left.children.pop() {
+            moved += child.size;
+            right.children.insert(0, child);
+        }
+        left.size -= moved;
+        right.size += moved;
+    }
+
+    /// Moves the first key of the child right of `separator` up,
+    /// and the separator down to the end of the child on its left
+    fn rotate_left(&mut self, separator: usize) {
+        let (before, after) = self.children.split_at_mut(separator + 1);
+        let (left, right) = (&mut before[separator], &mut after[0]);
+        let key = right.keys.remove(0);
+        let value = right.values.remove(0);
+        left.keys.push(mem::replace(&mut self.keys[separator], key));
+        left.values
+            .push(mem::replace(&mut self.values[separator], value));
+        let mut moved = 1;
+        if !right.is_leaf() {
+            let child = right.children.remove(0);
+            moved += child.size;
+            left.children.push(child);
+        }
+        left.size += moved;
+        right.size -= moved;
+    }
+
+    /// Joins the children on both sides of `separator` and the separator into one node
+    fn merge(&mut self, separator: usize) {
+        let right = self.children.remove(separator + 1);
+        let key = self.keys.remove(separator);
+        let value = self.values.remove(separator);
+        let left = &mut self.children[separator];
+        left.keys.push(key);
+        left.values.push(value);
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+        left.size += 1 + right.size;
+    }
+}
+
+impl<K, V> Default for BTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> BTree<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self::with_order(DEFAULT_ORDER)
+    }
+
+    /// B-tree with at most `order` children per node
+    ///
+    /// # Panics
+    ///
+    /// If `order` is less than 3
+    pub fn with_order(order: usize) -> Self {
+        assert!(
+            order >= 3,
+            "a B-tree needs at least 3 children per node, got {order}"
+        );
+        Self {
+            root: Node::new(),
+            order,
+        }
+    }
+
+    fn max_keys(&self) -> usize {
+        self.order - 1
+    }
+
+    fn min_keys(&self) -> usize {
+        self.order.div_ceil(2) - 1
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut node = &self.root;
+        loop {
+            match node.search(key) {
+                Ok(index) => return Some(&node.values[index]),
+                Err(_) if node.is_leaf() => return None,
+                Err(index) => node = &node.children[index],
+            }
+        }
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Number of keys in the tree
+    pub fn len(&self) -> usize {
+        self.root.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.size == 0
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        let max_keys = self.max_keys();
+        if let Insertion::Split(key, value, right) = self.root.insert(key, value, max_keys) {
+            let left = mem::replace(&mut self.root, Node::new());
+            self.root = Node {
+                keys: vec![key],
+                values: vec![value],
+                size: left.size + 1 + right.size,
+                children: vec![left, right],
+            };
+        }
+    }
+
+    /// The least key and its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(first) = node.children.first() {
+            node = first;
+        }
+        node.keys.first().zip(node.values.first())
+    }
+
+    /// The greatest key and its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        while let Some(last) = node.children.last() {
+            node = last;
+        }
+        node.keys.last().zip(node.values.last())
+    }
+
+    /// The greatest key less than or equal to `key` and its value
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        let mut floor = None;
+        loop {
+            match node.search(key) {
+                Ok(index) => return Some(node.entry(index)),
+                Err(index) => {
+                    if index > 0 {
+                        floor = Some(node.entry(index - 1));
+                    }
+                    if node.is_leaf() {
+                        return floor;
+                    }
+                    node = &node.children[index];
+                }
+            }
+        }
+    }
+
+    /// The least key greater than or equal to `key` and its value
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        let mut node = &self.root;
+        let mut ceiling = None;
+        loop {
+            match node.search(key) {
+                Ok(index) => return Some(node.entry(index)),
+                Err(index) => {
+                    if index < node.keys.len() {
+                        ceiling = Some(node.entry(index));
+                    }
+                    if node.is_leaf() {
+                        return ceiling;
+                    }
+                    node = &node.children[index];
+                }
+            }
+        }
+    }
+
+    /// Number of keys less than `key`
+    pub fn rank(&self, key: &K) -> usize {
+        let mut node = &self.root;
+        let mut rank = 0;
+        loop {
+            match node.search(key) {
+                Ok(index) => return rank + index + node.size_of_children_before(index + 1),
+                Err(index) => {
+                    rank += index + node.size_of_children_before(index);
+                    if node.is_leaf() {
+                        return rank;
+                    }
+                    node = &node.children[index];
+                }
+            }
+        }
+    }
+
+    /// The key of rank `rank` (exactly `rank` keys are less than it) and its value,
+    /// `None` if there are not that many keys
+    pub fn select(&self, mut rank: usize) -> Option<(&K, &V)> {
+        if rank >= self.len() {
+            return None;
+        }
+        let mut node = &self.root;
+        'nodes: loop {
+            if node.is_leaf() {
+                return Some(node.entry(rank));
+            }
+            for (index, child) in node.children.iter().enumerate() {
+                if rank < child.size {
+                    node = child;
+                    continue 'nodes;
+                }
+                rank -= child.size;
+                if rank == 0 {
+                    return Some(node.entry(index));
+                }
+                rank -= 1;
+            }
+            unreachable!("sizes of the children add up to the size of the node");
+        }
+    }
+
+    /// Keys in `range` in ascending order
+    pub fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+    where
+        R: RangeBounds<K>,
+    {
+        self.range(range).map(|(key, _)| key).collect()
+    }
+
+    /// Number of keys in `range`, found by ranks of its bounds without visiting the keys
+    pub fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>,
+    {
+        let below_start = match range.start_bound() {
+            Bound::Included(start) => self.rank(start),
+            Bound::Excluded(start) => self.rank(start) + usize::from(self.contains(start)),
+            Bound::Unbounded => 0,
+        };
+        let below_end = match range.end_bound() {
+            Bound::Included(end) => self.rank(end) + usize::from(self.contains(end)),
+            Bound::Excluded(end) => self.rank(end),
+            Bound::Unbounded => self.len(),
+        };
+        below_end.saturating_sub(below_start)
+    }
+
+    /// Keys and values in ascending order of the keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Keys in `range` and their values in ascending order of the keys
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let mut iter = Iter {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: self.size_in_range((range.start_bound(), range.end_bound())),
+        };
+        let mut node = &self.root;
+        loop {
+            let start = match range.start_bound() {
+                Bound::Included(start) => node.keys.partition_point(|key| key < start),
+                Bound::Excluded(start) => node.keys.partition_point(|key| key <= start),
+                Bound::Unbounded => 0,
+            };
+            if start < node.keys.len() {
+                iter.front.push((node, start));
+            }
+            if node.is_leaf() {
+                break;
+            }
+            node = &node.children[start];
+        }
+        let mut node = &self.root;
+        loop {
+            let end = match range.end_bound() {
+                Bound::Included(end) => node.keys.partition_point(|key| key <= end),
+                Bound::Excluded(end) => node.keys.partition_point(|key| key < end),
+                Bound::Unbounded => node.keys.len(),
+            };
+            if end > 0 {
+                iter.back.push((node, end));
+            }
+            if node.is_leaf() {
+                break;
+            }
+            node = &node.children[end];
+        }
+        iter
+    }
+
+    /// Removes the least key, returns it with its value
+    pub fn delete_min(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+        let min_keys = self.min_keys();
+        let removed = self.root.remove_min(min_keys);
+        self.shrink_root();
+        Some(removed)
+    }
+
+    /// Removes the greatest key, returns it with its value
+    pub fn delete_max(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+        let min_keys = self.min_keys();
+        let removed = self.root.remove_max(min_keys);
+        self.shrink_root();
+        Some(removed)
+    }
+
+    pub fn delete<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let min_keys = self.min_keys();
+        if self.root.remove(key, min_keys).is_some() {
+            self.shrink_root();
+        }
+    }
+
+    /// The root may run out of keys after a merge of its last two children,
+    /// the merged child becomes the new root
+    fn shrink_root(&mut self) {
+        if self.root.keys.is_empty() {
+            if let Some(child) = self.root.children.pop() {
+                self.root = child;
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    /// Nodes with keys left from the front, each with the index of its next key;
+    /// the deepest node is last
+    front: Vec<(&'a Node<K, V>, usize)>,
+    /// Nodes with keys left from the back, each with the index after its next key
+    back: Vec<(&'a Node<K, V>, usize)>,
+    /// Keeps the two ends from passing each other
+    remaining: usize,
+}
+
+impl<'a, K, V> IntoIterator for &'a BTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (node, index) = self.front.pop()?;
+        if index + 1 < node.keys.len() {
+            self.front.push((node, index + 1));
+        }
+        let mut child = node.children.get(index + 1);
+        while let Some(next) = child {
+            self.front.push((next, 0));
+            child = next.children.first();
+        }
+        Some(node.entry(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (node, end) = self.back.pop()?;
+        let index = end - 1;
+        if index > 0 {
+            self.back.push((node, index));
+        }
+        let mut child = node.children.get(index);
+        while let Some(next) = child {
+            self.back.push((next, next.keys.len()));
+            child = next.children.last();
+        }
+        Some(node.entry(index))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> where K: Ord {}
+
+pub struct Keys<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> where K: Ord {}
+
+pub struct Values<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> where K: Ord {}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::b_tree::{BTree, Node};
+    use crate::collection::test_utils::assert_stays_balanced;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, expected};
+
+    /// Depth of the leaves below `node` if all of them are on the same level and every node
+    /// has a valid number of sorted keys and children with the right sizes
+    fn leaf_depth<K, V>(node: &Node<K, V>, min_keys: usize, max_keys: usize) -> Option<usize>
+    where
+        K: Ord,
+    {
+        let keys_sorted = node.keys.windows(2).all(|pair| pair[0] < pair[1]);
+        let size = node.keys.len() + node.size_of_children_before(node.children.len());
+        if !keys_sorted
+            || node.keys.len() < min_keys
+            || node.keys.len() > max_keys
+            || node.values.len() != node.keys.len()
+            || node.size != size
+        {
+            return None;
+        }
+        if node.is_leaf() {
+            return Some(0);
+        }
+        if node.children.len() != node.keys.len() + 1 {
+            return None;
+        }
+        let depths: Option<Vec<usize>> = node
+            .children
+            .iter()
+            .map(|child| leaf_depth(child, min_keys, max_keys))
+            .collect();
+        let depths = depths?;
+        if depths.iter().any(|depth| *depth != depths[0]) {
+            return None;
+        }
+        Some(depths[0] + 1)
+    }
+
+    fn is_balanced<K, V>(tree: &BTree<K, V>) -> bool
+    where
+        K: Ord,
+    {
+        if tree.root.keys.is_empty() {
+            return tree.root.is_leaf() && tree.root.size == 0;
+        }
+        // the root may have as few as one key
+        let root_min_keys = 1;
+        let children_balanced = tree
+            .root
+            .children
+            .iter()
+            .map(|child| leaf_depth(child, tree.min_keys(), tree.max_keys()))
+            .collect::<Option<Vec<usize>>>()
+            .is_some_and(|depths| depths.windows(2).all(|pair| pair[0] == pair[1]));
+        tree.root.keys.len() >= root_min_keys
+            && tree.root.keys.len() <= tree.max_keys()
+            && (tree.root.is_leaf() || tree.root.children.len() == tree.root.keys.len() + 1)
+            && children_balanced
+    }
+
+    #[test]
+    fn stays_balanced_for_every_order() {
+        for order in [3, 4, 5, 8, 16] {
+            assert_stays_balanced(
+                || BTree::with_order(order),
+                |tree| BooleanAssert::assert_that(is_balanced(tree)).is_true(),
+            );
+        }
+    }
+
+    #[test]
+    fn range_from_both_ends_for_every_order() {
+        for order in [3, 4, 5, 8, 16] {
+            let mut tree = BTree::with_order(order);
+            // 389 is prime, so the keys come in scattered
+            for step in 0..389 {
+                let key = step * 97 % 389;
+                tree.put(key, key * 2);
+            }
+            for key in (0..389).step_by(3) {
+                tree.delete(&key);
+            }
+            let kept = |key: &u32| !key.is_multiple_of(3);
+
+            BooleanAssert::assert_that(tree.keys().copied().eq((0..389).filter(kept))).is_true();
+            BooleanAssert::assert_that(
+                tree.values()
+                    .rev()
+                    .copied()
+                    .eq((0..389).filter(kept).map(|key| key * 2).rev()),
+            )
+            .is_true();
+            for (start, end) in [(0, 0), (10, 50), (100, 388), (200, 400)] {
+                BooleanAssert::assert_that(
+                    tree.range(start..=end)
+                        .rev()
+                        .map(|(key, _)| *key)
+                        .eq((start..=end.min(388)).filter(kept).rev()),
+                )
+                .is_true();
+                BooleanAssert::assert_that(
+                    tree.range(start..end)
+                        .map(|(key, _)| *key)
+                        .eq((start..end.min(389)).filter(kept)),
+                )
+                .is_true();
+            }
+        }
+    }
+
+    #[test]
+    fn wider_nodes_make_shallower_trees() {
+        let depth = |order| {
+            let mut tree = BTree::with_order(order);
+            for key in 0..10_000 {
+                tree.put(key, ());
+            }
+            BooleanAssert::assert_that(is_balanced(&tree)).is_true();
+            let mut depth = 0;
+            let mut node = &tree.root;
+            while let Some(first) = node.children.first() {
+                node = first;
+                depth += 1;
+            }
+            depth
+        };
+
+        NumericAssert::assert_that(actual(depth(64)))
+            .is_less()
+            .than(expected(depth(4)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn order_too_small() {
+        let _: BTree<u32, u32> = BTree::with_order(2);
+    }
+}
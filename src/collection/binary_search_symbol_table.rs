@@ -1,9 +1,12 @@
+use std::borrow::Borrow;
 use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::ops::{Bound, RangeBounds};
 
+/// Ordered symbol table on two parallel sorted arrays: lookups and ordered queries
+/// are binary searches, inserts and deletes shift the elements after the position
 pub struct SymbolTable<Key, Value>
 where
-    Key: Ord + Debug,
+    Key: Ord,
 {
     keys: Vec<Key>,
     values: Vec<Value>,
@@ -11,7 +14,7 @@ where
 
 impl<Key, Value> SymbolTable<Key, Value>
 where
-    Key: Ord + Debug,
+    Key: Ord,
 {
     pub fn new() -> SymbolTable<Key, Value> {
         SymbolTable {
@@ -36,22 +39,148 @@ where
         }
     }
 
-    pub fn get(&self, key: Key) -> Option<&Value> {
-        let (current_index, _) = self.index(&key);
-        return if let Some(current_index) = current_index {
+    pub fn get<Q>(&self, key: &Q) -> Option<&Value>
+    where
+        Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (current_index, _) = self.index(key);
+        if let Some(current_index) = current_index {
             self.values.get(current_index)
         } else {
             None
+        }
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.index(key).0.is_some()
+    }
+
+    /// Number of keys in the table
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn delete<Q>(&mut self, key: &Q)
+    where
+        Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if let (Some(current_index), _) = self.index(key) {
+            self.keys.remove(current_index);
+            self.values.remove(current_index);
+        }
+    }
+
+    /// The least key and its value
+    pub fn min(&self) -> Option<(&Key, &Value)> {
+        self.select(0)
+    }
+
+    /// The greatest key and its value
+    pub fn max(&self) -> Option<(&Key, &Value)> {
+        self.keys.last().zip(self.values.last())
+    }
+
+    /// The greatest key less than or equal to `key` and its value
+    pub fn floor(&self, key: &Key) -> Option<(&Key, &Value)> {
+        match self.index(key) {
+            (Some(current_index), _) => self.select(current_index),
+            (None, 0) => None,
+            (None, needed_index) => self.select(needed_index - 1),
+        }
+    }
+
+    /// The least key greater than or equal to `key` and its value
+    pub fn ceiling(&self, key: &Key) -> Option<(&Key, &Value)> {
+        let (_, needed_index) = self.index(key);
+        self.select(needed_index)
+    }
+
+    /// Number of keys less than `key`
+    pub fn rank(&self, key: &Key) -> usize {
+        let (_, needed_index) = self.index(key);
+        needed_index
+    }
+
+    /// The key of rank `rank` (exactly `rank` keys are less than it) and its value,
+    /// `None` if there are not that many keys
+    pub fn select(&self, rank: usize) -> Option<(&Key, &Value)> {
+        self.keys.get(rank).zip(self.values.get(rank))
+    }
+
+    /// Keys in `range` in ascending order
+    pub fn keys_in_range<R>(&self, range: R) -> Vec<&Key>
+    where
+        R: RangeBounds<Key>,
+    {
+        let (start, end) = self.indices_of(&range);
+        self.keys[start..end].iter().collect()
+    }
+
+    /// Number of keys in `range`
+    pub fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<Key>,
+    {
+        let (start, end) = self.indices_of(&range);
+        end - start
+    }
+
+    /// Removes the least key, returns it with its value
+    pub fn delete_min(&mut self) -> Option<(Key, Value)> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        Some((self.keys.remove(0), self.values.remove(0)))
+    }
+
+    /// Removes the greatest key, returns it with its value
+    pub fn delete_max(&mut self) -> Option<(Key, Value)> {
+        self.keys.pop().zip(self.values.pop())
+    }
+
+    /// Index of the first key in `range` and the index after the last one
+    fn indices_of<R>(&self, range: &R) -> (usize, usize)
+    where
+        R: RangeBounds<Key>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.keys.partition_point(|key| key < start),
+            Bound::Excluded(start) => self.keys.partition_point(|key| key <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.keys.partition_point(|key| key <= end),
+            Bound::Excluded(end) => self.keys.partition_point(|key| key < end),
+            Bound::Unbounded => self.keys.len(),
         };
+        (start, end.max(start))
     }
 
-    fn index(&self, key: &Key) -> (Option<usize>, usize) {
+    /// Index of `key` if it is in the table, and the index where it belongs
+    fn index<Q>(&self, key: &Q) -> (Option<usize>, usize)
+    where
+        Key: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.keys.is_empty() {
+            return (None, 0);
+        }
         let mut low: usize = 0;
         let mut high: usize = self.keys.len();
         loop {
             let mid = (low + high) / 2;
             let mid_value = self.keys.get(mid);
-            let compare_result = mid_value.expect("unexpected empty value").cmp(key);
+            let compare_result = mid_value.expect("unexpected empty value").borrow().cmp(key);
             match compare_result {
                 Ordering::Less => {
                     low = mid + 1;
@@ -75,7 +204,7 @@ where
 
 impl<Key, Value> Default for SymbolTable<Key, Value>
 where
-    Key: Ord + Debug,
+    Key: Ord,
 {
     fn default() -> Self {
         Self::new()
@@ -115,7 +244,7 @@ mod test {
         symbol_table.put(1, "A");
         symbol_table.put(7, "K");
 
-        let result = symbol_table.get(7).copied().unwrap_or("IT IS EMPTY");
+        let result = symbol_table.get(&7).copied().unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
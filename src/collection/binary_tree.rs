@@ -1,6 +1,11 @@
+use crate::collection::search_tree;
+use crate::collection::search_tree::{InOrder, SearchNode};
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::RangeBounds;
 use std::ptr::NonNull;
 
 pub struct BinarySearchTree<K, V>
@@ -8,7 +13,6 @@ where
     K: Ord,
 {
     root: Link<K, V>,
-    length: usize,
     _boo_key: PhantomData<K>,
     _boo_value: PhantomData<V>,
 }
@@ -21,6 +25,8 @@ where
     value: V,
     left: Link<K, V>,
     right: Link<K, V>,
+    /// Number of nodes in the subtree rooted here
+    size: usize,
 }
 
 struct DeleteResult<K, V>
@@ -42,12 +48,54 @@ where
             value,
             left: None,
             right: None,
+            size: 1,
         }
     }
+
+    fn update_size(&mut self) {
+        self.size = 1 + size(self.left) + size(self.right);
+    }
+}
+
+fn size<K, V>(link: Link<K, V>) -> usize
+where
+    K: Ord,
+{
+    link.map_or(0, |node| unsafe { (*node.as_ptr()).size })
 }
 
 type Link<K, V> = Option<NonNull<Node<K, V>>>;
 
+/// Nodes are only reached through raw links, so the links are searched: a link handed out
+/// by the walk still allows writing to its node, which [`IterMut`] needs
+impl<K, V> SearchNode for NonNull<Node<K, V>>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        unsafe { &(*self.as_ptr()).key }
+    }
+
+    fn value(&self) -> &V {
+        unsafe { &(*self.as_ptr()).value }
+    }
+
+    fn left(&self) -> Option<&Self> {
+        unsafe { (*self.as_ptr()).left.as_ref() }
+    }
+
+    fn right(&self) -> Option<&Self> {
+        unsafe { (*self.as_ptr()).right.as_ref() }
+    }
+
+    fn size(&self) -> usize {
+        unsafe { (*self.as_ptr()).size }
+    }
+}
+
 impl<K, V> Default for BinarySearchTree<K, V>
 where
     K: Ord,
@@ -64,86 +112,158 @@ where
     pub fn new() -> Self {
         Self {
             root: None,
-            length: 0,
             _boo_key: PhantomData,
             _boo_value: PhantomData,
         }
     }
 
-    pub fn get(&self, key: K) -> Option<&V> {
-        unsafe {
-            let mut current = self.root.map(|node| &(*node.as_ptr()));
-            loop {
-                match current {
-                    None => {
-                        break;
-                    }
-                    Some(link) => {
-                        let compare_result = key.cmp(&link.key);
-                        match compare_result {
-                            Ordering::Less => current = link.left.map(|node| &(*node.as_ptr())),
-                            Ordering::Equal => {
-                                return Some(&link.value);
-                            }
-                            Ordering::Greater => current = link.right.map(|node| &(*node.as_ptr())),
-                        }
-                    }
-                }
-            }
-        }
-        None
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_ref(), key).map(SearchNode::value)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_ref(), key).is_some()
+    }
+
+    /// Number of keys in the tree
+    pub fn len(&self) -> usize {
+        size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
     }
 
     pub fn put(&mut self, key: K, value: V) {
         self.root = BinarySearchTree::upsert(self.root, key, value);
-        self.length += 1;
     }
 
-    pub fn min(&self) -> Option<&V> {
-        return match self.root {
-            None => None,
-            Some(root_link) => unsafe {
-                let current = Self::find_min(root_link);
-                Some(&current.value)
-            },
-        };
+    /// The least key and its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min(self.root.as_ref()).map(SearchNode::entry)
+    }
+
+    /// The greatest key and its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max(self.root.as_ref()).map(SearchNode::entry)
+    }
+
+    /// The greatest key less than or equal to `key` and its value
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::floor(self.root.as_ref(), key).map(SearchNode::entry)
+    }
+
+    /// The least key greater than or equal to `key` and its value
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::ceiling(self.root.as_ref(), key).map(SearchNode::entry)
     }
 
-    pub fn max(&self) -> Option<&V> {
+    /// Number of keys less than `key`
+    pub fn rank(&self, key: &K) -> usize {
+        search_tree::rank(self.root.as_ref(), key)
+    }
+
+    /// The key of rank `rank` (exactly `rank` keys are less than it) and its value,
+    /// `None` if there are not that many keys
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        search_tree::select(self.root.as_ref(), rank).map(SearchNode::entry)
+    }
+
+    /// Keys in `range` in ascending order
+    pub fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+    where
+        R: RangeBounds<K>,
+    {
+        self.range(range).map(|(key, _)| key).collect()
+    }
+
+    /// Number of keys in `range`, found by ranks of its bounds without visiting the keys
+    pub fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>,
+    {
+        search_tree::size_in_range(self.root.as_ref(), &range)
+    }
+
+    /// Keys and values in ascending order of the keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
+    }
+
+    /// Same as [`BinarySearchTree::iter`] with mutable values
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            in_order: InOrder::new(self.root.as_ref(), &..),
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Keys in `range` and their values in ascending order of the keys
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Iter {
+            in_order: InOrder::new(self.root.as_ref(), &range),
+        }
+    }
+
+    /// Every node before its subtrees, the left subtree before the right one
+    pub fn pre_order(&self) -> Traversal<'_, K, V> {
+        Traversal::new(self, TraversalOrder::Pre)
+    }
+
+    /// Every node after its subtrees, the left subtree before the right one
+    pub fn post_order(&self) -> Traversal<'_, K, V> {
+        Traversal::new(self, TraversalOrder::Post)
+    }
+
+    /// Nodes level by level from the root, every level from left to right
+    pub fn level_order(&self) -> Traversal<'_, K, V> {
+        Traversal::new(self, TraversalOrder::Level)
+    }
+
+    /// Removes the least key, returns it with its value
+    pub fn delete_min(&mut self) -> Option<(K, V)> {
+        let root = self.root?;
         unsafe {
-            match self.root {
-                None => None,
-                Some(root_link) => {
-                    let mut current = &(*root_link.as_ptr());
-                    while current.right.is_some() {
-                        match current.right {
-                            None => {
-                                break;
-                            }
-                            Some(link) => {
-                                current = &(*link.as_ptr());
-                            }
-                        }
-                    }
-                    Some(&current.value)
-                }
-            }
+            let result = Self::_delete_min(root);
+            self.root = result.new_link;
+            Some((result.deleted_key, result.deleted_value))
         }
     }
 
-    pub fn delete_min(&mut self) {
-        match self.root {
-            None => {
-                //do nothing
-            }
-            Some(root) => unsafe {
-                let result = Self::_delete_min(root);
-                self.root = result.new_link;
-            },
+    /// Removes the greatest key, returns it with its value
+    pub fn delete_max(&mut self) -> Option<(K, V)> {
+        let root = self.root?;
+        unsafe {
+            let result = Self::_delete_max(root);
+            self.root = result.new_link;
+            Some((result.deleted_key, result.deleted_value))
         }
     }
 
-    pub fn delete(&mut self, key: K) {
+    pub fn delete<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         match self.root {
             None => {
                 //do nothing
@@ -163,29 +283,25 @@ where
             }
         }
     }
-    unsafe fn find_min<'a>(root_link: NonNull<Node<K, V>>) -> &'a Node<K, V> {
-        let mut current = root_link.as_ref();
-        while current.left.is_some() {
-            match current.left {
-                None => {
-                    break;
-                }
-                Some(link) => {
-                    current = link.as_ref();
-                }
-            }
-        }
-        current
+
+    /// Nodes are owned by the tree, so they live as long as the link to them is borrowed
+    fn as_node(link: &Link<K, V>) -> Option<&Node<K, V>> {
+        link.map(|node| unsafe { &*node.as_ptr() })
     }
 
-    unsafe fn _delete(root: NonNull<Node<K, V>>, key: K) -> Option<DeleteResult<K, V>> {
+    unsafe fn _delete<Q>(root: NonNull<Node<K, V>>, key: &Q) -> Option<DeleteResult<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let root_ptr = root.as_ptr();
-        let compare_result = key.cmp(&(*root_ptr).key);
+        let compare_result = key.cmp((*root_ptr).key.borrow());
         match compare_result {
             Ordering::Less => {
                 let left = (*root_ptr).left?;
                 let result = Self::_delete(left, key)?;
                 (*root_ptr).left = result.new_link;
+                (*root_ptr).update_size();
                 Some(DeleteResult {
                     new_link: Some(root),
                     deleted_key: result.deleted_key,
@@ -196,6 +312,7 @@ where
                 let right = (*root_ptr).right?;
                 let result = Self::_delete(right, key)?;
                 (*root_ptr).right = result.new_link;
+                (*root_ptr).update_size();
                 Some(DeleteResult {
                     new_link: Some(root),
                     deleted_key: result.deleted_key,
@@ -207,6 +324,8 @@ where
                     (Some(_), Some(right)) => {
                         // find a min in the right sub tree
                         let deleted_min_result = Self::_delete_min(right);
+                        (*root_ptr).right = deleted_min_result.new_link;
+                        (*root_ptr).update_size();
                         let deleted_key =
                             mem::replace(&mut (*root_ptr).key, deleted_min_result.deleted_key);
                         let deleted_value =
@@ -262,6 +381,32 @@ where
             Some(left) => {
                 let result = Self::_delete_min(left);
                 (*root_ref).left = result.new_link;
+                (*root_ref).update_size();
+                DeleteResult {
+                    new_link: Some(root),
+                    deleted_key: result.deleted_key,
+                    deleted_value: result.deleted_value,
+                }
+            }
+        }
+    }
+
+    unsafe fn _delete_max(root: NonNull<Node<K, V>>) -> DeleteResult<K, V> {
+        let root_ref = root.as_ptr();
+
+        match (*root_ref).right {
+            None => {
+                let v = Box::from_raw(root_ref);
+                DeleteResult {
+                    new_link: v.left,
+                    deleted_key: v.key,
+                    deleted_value: v.value,
+                }
+            }
+            Some(right) => {
+                let result = Self::_delete_max(right);
+                (*root_ref).right = result.new_link;
+                (*root_ref).update_size();
                 DeleteResult {
                     new_link: Some(root),
                     deleted_key: result.deleted_key,
@@ -320,6 +465,7 @@ where
                                 BinarySearchTree::upsert((*ptr_node).right, key, value);
                         }
                     };
+                    (*ptr_node).update_size();
                 }
                 node_link
             }
@@ -336,13 +482,317 @@ where
     }
 }
 
+pub struct Iter<'a, K, V>
+where
+    K: Ord,
+{
+    in_order: InOrder<'a, NonNull<Node<K, V>>>,
+}
+
+impl<'a, K, V> IntoIterator for &'a BinarySearchTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.in_order.next().map(SearchNode::entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.in_order.remaining, Some(self.in_order.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.in_order.next_back().map(SearchNode::entry)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> where K: Ord {}
+
+pub struct IterMut<'a, K, V>
+where
+    K: Ord,
+{
+    in_order: InOrder<'a, NonNull<Node<K, V>>>,
+    _boo: PhantomData<&'a mut Node<K, V>>,
+}
+
+impl<'a, K, V> IntoIterator for &'a mut BinarySearchTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // every node is given out once, so the mutable references never alias
+        self.in_order.next().map(|node| unsafe {
+            let node = node.as_ptr();
+            (&(*node).key, &mut (*node).value)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.in_order.remaining, Some(self.in_order.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.in_order.next_back().map(|node| unsafe {
+            let node = node.as_ptr();
+            (&(*node).key, &mut (*node).value)
+        })
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> where K: Ord {}
+
+pub struct IntoIter<K, V> {
+    entries: std::vec::IntoIter<(K, V)>,
+}
+
+impl<K, V> IntoIterator for BinarySearchTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Takes the nodes apart in order, so the tree is not needed during iteration
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut entries = Vec::with_capacity(self.len());
+        let mut ancestors: Vec<Box<Node<K, V>>> = Vec::new();
+        let mut current = self.root.take();
+        loop {
+            while let Some(node) = current {
+                let mut node = unsafe { Box::from_raw(node.as_ptr()) };
+                current = node.left.take();
+                ancestors.push(node);
+            }
+            let Some(node) = ancestors.pop() else {
+                break;
+            };
+            let Node {
+                key, value, right, ..
+            } = *node;
+            entries.push((key, value));
+            current = right;
+        }
+        IntoIter {
+            entries: entries.into_iter(),
+        }
+    }
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+pub struct Keys<'a, K, V>
+where
+    K: Ord,
+{
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> where K: Ord {}
+
+pub struct Values<'a, K, V>
+where
+    K: Ord,
+{
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> where K: Ord {}
+
+enum TraversalOrder {
+    Pre,
+    Post,
+    Level,
+}
+
+/// Pre-order, post-order or level-order walk, made by [`BinarySearchTree::pre_order`],
+/// [`BinarySearchTree::post_order`] and [`BinarySearchTree::level_order`]
+pub struct Traversal<'a, K, V>
+where
+    K: Ord,
+{
+    order: TraversalOrder,
+    /// Nodes to visit, with a flag for post-order telling that their children were already added
+    pending: VecDeque<(&'a Node<K, V>, bool)>,
+}
+
+impl<'a, K, V> Traversal<'a, K, V>
+where
+    K: Ord,
+{
+    fn new(tree: &'a BinarySearchTree<K, V>, order: TraversalOrder) -> Traversal<'a, K, V> {
+        Traversal {
+            order,
+            pending: BinarySearchTree::as_node(&tree.root)
+                .map(|root| (root, false))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn push_children(&mut self, first: &'a Link<K, V>, second: &'a Link<K, V>) {
+        for child in [first, second] {
+            if let Some(child) = BinarySearchTree::as_node(child) {
+                self.pending.push_back((child, false));
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Traversal<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = match self.order {
+            TraversalOrder::Pre => {
+                let (node, _) = self.pending.pop_back()?;
+                self.push_children(&node.right, &node.left);
+                node
+            }
+            TraversalOrder::Post => loop {
+                let (node, children_added) = self.pending.pop_back()?;
+                if children_added {
+                    break node;
+                }
+                self.pending.push_back((node, true));
+                self.push_children(&node.right, &node.left);
+            },
+            TraversalOrder::Level => {
+                let (node, _) = self.pending.pop_front()?;
+                self.push_children(&node.left, &node.right);
+                node
+            }
+        };
+        Some((&node.key, &node.value))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::collection::binary_tree::BinarySearchTree;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
     use easy_assert::num_assertions::NumericAssert;
     use easy_assert::option_assertions::OptionAssert;
     use easy_assert::string_assertions::StringAssert;
-    use easy_assert::{actual, expected};
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    /// Tree with keys of `S E A R C H E X A M P L E`, the value of a key is the position
+    /// of its last occurrence
+    fn searchexample() -> BinarySearchTree<char, usize> {
+        let mut tree = BinarySearchTree::new();
+        for (position, key) in "SEARCHEXAMPLE".chars().enumerate() {
+            tree.put(key, position);
+        }
+        tree
+    }
 
     #[test]
     fn basics_push() {
@@ -353,12 +803,12 @@ mod test {
         tree.put(7, "K");
         tree.put(12, "U");
 
-        NumericAssert::assert_that(actual(tree.length))
+        NumericAssert::assert_that(actual(tree.len()))
             .is_equal()
             .to(expected(5));
         unsafe {
             let root = tree.root.expect("not null").as_ref();
-            NumericAssert::assert_that(actual(root.key.clone()))
+            NumericAssert::assert_that(actual(root.key))
                 .is_equal()
                 .to(expected(10));
 
@@ -367,7 +817,7 @@ mod test {
                 .to(expected("T".to_string()));
 
             let root_left_child = root.left.expect("not null").as_ref();
-            NumericAssert::assert_that(actual(root_left_child.key.clone()))
+            NumericAssert::assert_that(actual(root_left_child.key))
                 .is_equal()
                 .to(expected(4));
 
@@ -376,7 +826,7 @@ mod test {
                 .to(expected("D".to_string()));
 
             let root_right_child = root.right.expect("not null").as_ref();
-            NumericAssert::assert_that(actual(root_right_child.key.clone()))
+            NumericAssert::assert_that(actual(root_right_child.key))
                 .is_equal()
                 .to(expected(12));
 
@@ -385,7 +835,7 @@ mod test {
                 .to(expected("U".to_string()));
 
             let child_left_child = root_left_child.left.expect("not null").as_ref();
-            NumericAssert::assert_that(actual(child_left_child.key.clone()))
+            NumericAssert::assert_that(actual(child_left_child.key))
                 .is_equal()
                 .to(expected(1));
 
@@ -394,7 +844,7 @@ mod test {
                 .to(expected("A".to_string()));
 
             let child_right_child = root_left_child.right.expect("not null").as_ref();
-            NumericAssert::assert_that(actual(child_right_child.key.clone()))
+            NumericAssert::assert_that(actual(child_right_child.key))
                 .is_equal()
                 .to(expected(7));
 
@@ -412,7 +862,7 @@ mod test {
         tree.put(1, "A");
         tree.put(7, "K");
 
-        let result = tree.get(7).copied().unwrap_or("IT IS EMPTY");
+        let result = tree.get(&7).copied().unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
@@ -427,7 +877,7 @@ mod test {
         tree.put(1, "A");
         tree.put(7, "K");
 
-        let result = tree.min().copied().unwrap_or("IT IS EMPTY");
+        let result = tree.min().map(|(_, value)| *value).unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
@@ -442,7 +892,7 @@ mod test {
         tree.put(1, "A");
         tree.put(7, "K");
 
-        let result = tree.max().copied().unwrap_or("IT IS EMPTY");
+        let result = tree.max().map(|(_, value)| *value).unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
@@ -458,7 +908,7 @@ mod test {
         tree.put(1, "A");
         tree.put(7, "K");
 
-        let result = tree.min().copied().unwrap_or("IT IS EMPTY");
+        let result = tree.min().map(|(_, value)| *value).unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
@@ -468,7 +918,7 @@ mod test {
         tree.delete_min();
 
         // THEN
-        let result = tree.min().copied().unwrap_or("IT IS EMPTY");
+        let result = tree.min().map(|(_, value)| *value).unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
@@ -484,18 +934,233 @@ mod test {
         tree.put(1, "A");
         tree.put(7, "K");
 
-        let result = tree.get(7).copied().unwrap_or("IT IS EMPTY");
+        let result = tree.get(&7).copied().unwrap_or("IT IS EMPTY");
 
         StringAssert::assert_that(actual(result.to_string()))
             .is_equal()
             .to(expected("K".to_string()));
 
         // WHEN
-        tree.delete(7);
+        tree.delete(&7);
 
         // THEN
-        let result = tree.get(7).copied();
+        let result = tree.get(&7).copied();
 
         OptionAssert::assert_that(result).is_none();
     }
+
+    #[test]
+    fn put_existing_key() {
+        let tree = searchexample();
+
+        NumericAssert::assert_that(actual(tree.len()))
+            .is_equal()
+            .to(expected(10));
+        NumericAssert::assert_that(actual(tree.get(&'E').copied().unwrap_or(0)))
+            .is_equal()
+            .to(expected(12));
+    }
+
+    #[test]
+    fn min_max_keys() {
+        let tree = searchexample();
+
+        BooleanAssert::assert_that(tree.min() == Some((&'A', &8))).is_true();
+        BooleanAssert::assert_that(tree.max() == Some((&'X', &7))).is_true();
+    }
+
+    #[test]
+    fn floor_ceiling() {
+        let tree = searchexample();
+
+        BooleanAssert::assert_that(tree.floor(&'G').map(|(key, _)| *key) == Some('E')).is_true();
+        BooleanAssert::assert_that(tree.floor(&'H').map(|(key, _)| *key) == Some('H')).is_true();
+        BooleanAssert::assert_that(tree.ceiling(&'Q').map(|(key, _)| *key) == Some('R')).is_true();
+        BooleanAssert::assert_that(tree.ceiling(&'Y').is_none()).is_true();
+        BooleanAssert::assert_that(tree.floor(&'0').is_none()).is_true();
+    }
+
+    #[test]
+    fn rank_select() {
+        let tree = searchexample();
+        let keys = ['A', 'C', 'E', 'H', 'L', 'M', 'P', 'R', 'S', 'X'];
+
+        for (rank, key) in keys.iter().enumerate() {
+            NumericAssert::assert_that(actual(tree.rank(key)))
+                .is_equal()
+                .to(expected(rank));
+            BooleanAssert::assert_that(tree.select(rank).map(|(key, _)| key) == Some(key))
+                .is_true();
+        }
+        NumericAssert::assert_that(actual(tree.rank(&'N')))
+            .is_equal()
+            .to(expected(6));
+        BooleanAssert::assert_that(tree.select(10).is_none()).is_true();
+    }
+
+    #[test]
+    fn ranges() {
+        let tree = searchexample();
+
+        let keys: Vec<char> = tree.keys_in_range('F'..='R').into_iter().copied().collect();
+
+        ListAssert::assert_that(actual_vec(keys))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!['H', 'L', 'M', 'P', 'R']))
+            .in_order();
+        NumericAssert::assert_that(actual(tree.size_in_range('F'..='R')))
+            .is_equal()
+            .to(expected(5));
+        NumericAssert::assert_that(actual(tree.size_in_range('F'..'R')))
+            .is_equal()
+            .to(expected(4));
+        NumericAssert::assert_that(actual(tree.size_in_range('S'..)))
+            .is_equal()
+            .to(expected(2));
+        NumericAssert::assert_that(actual(tree.size_in_range('R'..'F')))
+            .is_equal()
+            .to(expected(0));
+    }
+
+    #[test]
+    fn delete_max() {
+        let mut tree = searchexample();
+
+        BooleanAssert::assert_that(tree.delete_max() == Some(('X', 7))).is_true();
+        BooleanAssert::assert_that(tree.delete_max() == Some(('S', 0))).is_true();
+        BooleanAssert::assert_that(tree.max().map(|(key, _)| *key) == Some('R')).is_true();
+        NumericAssert::assert_that(actual(tree.len()))
+            .is_equal()
+            .to(expected(8));
+    }
+
+    #[test]
+    fn contains() {
+        let mut tree = searchexample();
+        tree.delete(&'E');
+
+        BooleanAssert::assert_that(tree.contains(&'S')).is_true();
+        BooleanAssert::assert_that(tree.contains(&'E')).is_false();
+        BooleanAssert::assert_that(tree.contains(&'B')).is_false();
+    }
+
+    fn keys_of<'a>(entries: impl Iterator<Item = (&'a char, &'a usize)>) -> String {
+        entries.map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn iter_from_both_ends() {
+        let tree = searchexample();
+
+        StringAssert::assert_that(actual(keys_of(tree.iter())))
+            .is_equal()
+            .to(expected("ACEHLMPRSX".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.iter().rev())))
+            .is_equal()
+            .to(expected("XSRPMLHECA".to_string()));
+
+        let mut iter = tree.iter();
+        let mut meeting = String::new();
+        while let (Some((first, _)), Some((last, _))) = (iter.next(), iter.next_back()) {
+            meeting.push(*first);
+            meeting.push(*last);
+        }
+        StringAssert::assert_that(actual(meeting))
+            .is_equal()
+            .to(expected("AXCSERHPLM".to_string()));
+        NumericAssert::assert_that(actual(iter.len()))
+            .is_equal()
+            .to(expected(0));
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut tree = searchexample();
+
+        for (_, value) in tree.iter_mut() {
+            *value *= 10;
+        }
+
+        let values: Vec<usize> = tree.values().copied().collect();
+        ListAssert::assert_that(actual_vec(values))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![80, 40, 120, 50, 110, 90, 100, 30, 0, 70]))
+            .in_order();
+    }
+
+    #[test]
+    fn into_iter() {
+        let tree = searchexample();
+
+        let keys: String = tree.keys().rev().collect();
+        let entries: Vec<(char, usize)> = tree.into_iter().collect();
+
+        StringAssert::assert_that(actual(keys))
+            .is_equal()
+            .to(expected("XSRPMLHECA".to_string()));
+        BooleanAssert::assert_that(entries[..3] == [('A', 8), ('C', 4), ('E', 12)]).is_true();
+        NumericAssert::assert_that(actual(entries.len()))
+            .is_equal()
+            .to(expected(10));
+    }
+
+    #[test]
+    fn range() {
+        let tree = searchexample();
+
+        StringAssert::assert_that(actual(keys_of(tree.range('F'..='R'))))
+            .is_equal()
+            .to(expected("HLMPR".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.range('F'..'R').rev())))
+            .is_equal()
+            .to(expected("PMLH".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.range(..'E'))))
+            .is_equal()
+            .to(expected("AC".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.range('T'..))))
+            .is_equal()
+            .to(expected("X".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.range('N'..'O'))))
+            .is_equal()
+            .to(expected("".to_string()));
+    }
+
+    #[test]
+    fn traversals() {
+        let tree = searchexample();
+
+        StringAssert::assert_that(actual(keys_of(tree.pre_order())))
+            .is_equal()
+            .to(expected("SEACRHMLPX".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.post_order())))
+            .is_equal()
+            .to(expected("CALPMHREXS".to_string()));
+        StringAssert::assert_that(actual(keys_of(tree.level_order())))
+            .is_equal()
+            .to(expected("SEXARCHMLP".to_string()));
+        BooleanAssert::assert_that(
+            BinarySearchTree::<char, usize>::new()
+                .pre_order()
+                .next()
+                .is_none(),
+        )
+        .is_true();
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut tree: BinarySearchTree<String, usize> = BinarySearchTree::new();
+        for (position, word) in ["dog", "cat", "bird"].into_iter().enumerate() {
+            tree.put(word.to_string(), position);
+        }
+
+        NumericAssert::assert_that(actual(tree.get("cat").copied().unwrap_or(9)))
+            .is_equal()
+            .to(expected(1));
+        tree.delete("cat");
+        BooleanAssert::assert_that(tree.contains("cat")).is_false();
+        NumericAssert::assert_that(actual(tree.len()))
+            .is_equal()
+            .to(expected(2));
+    }
 }
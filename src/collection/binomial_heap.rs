@@ -0,0 +1,198 @@
+use crate::collection::priority_queue::PriorityQueue;
+use std::mem;
+
+/// Mergeable priority queue which gives out the greatest element first.
+///
+/// The heap is a forest of heap ordered binomial trees with at most one tree of every order,
+/// like the binary representation of its size. Insert, pop and meld add such forests
+/// as binary numbers, so all of them take logarithmic time.
+pub struct BinomialHeap<T> {
+    /// `trees[order]` is the tree of `2^order` elements if the heap has one
+    trees: Vec<Option<Box<Tree<T>>>>,
+    len: usize,
+}
+
+struct Tree<T> {
+    elem: T,
+    /// `children[order]` is a tree of that order
+    children: Vec<Box<Tree<T>>>,
+}
+
+impl<T> BinomialHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> BinomialHeap<T> {
+        BinomialHeap {
+            trees: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn from(elements: Vec<T>) -> BinomialHeap<T> {
+        let mut heap = BinomialHeap::new();
+        for elem in elements {
+            heap.insert(elem);
+        }
+
+        heap
+    }
+
+    /// Moves all elements of `other` into this heap in logarithmic time
+    pub fn meld(&mut self, other: BinomialHeap<T>) {
+        self.len += other.len;
+        self.add_trees(other.trees);
+    }
+
+    /// Adds the forest to the heap like adding binary numbers: two trees of the same order
+    /// are linked into a carry of the next order
+    fn add_trees(&mut self, trees: Vec<Option<Box<Tree<T>>>>) {
+        let mut trees = trees.into_iter();
+        let mut carry = None;
+        let mut order = 0;
+        loop {
+            let tree = trees.next();
+            if tree.is_none() && carry.is_none() {
+                break;
+            }
+            if order == self.trees.len() {
+                self.trees.push(None);
+            }
+            let own = self.trees[order].take();
+            (self.trees[order], carry) = match (own, tree.flatten(), carry) {
+                (None, None, None) => (None, None),
+                (Some(single), None, None)
+                | (None, Some(single), None)
+                | (None, None, Some(single)) => (Some(single), None),
+                (Some(first), Some(second), None)
+                | (Some(first), None, Some(second))
+                | (None, Some(first), Some(second)) => (None, Some(Self::link(first, second))),
+                (Some(first), Some(second), Some(third)) => {
+                    (Some(third), Some(Self::link(first, second)))
+                }
+            };
+            order += 1;
+        }
+    }
+
+    /// Makes the tree with the lesser root a child of the other one, both have the same order
+    fn link(mut first: Box<Tree<T>>, mut second: Box<Tree<T>>) -> Box<Tree<T>> {
+        if second.elem > first.elem {
+            mem::swap(&mut first, &mut second);
+        }
+        first.children.push(second);
+
+        first
+    }
+
+    fn max_order(&self) -> Option<usize> {
+        self.trees
+            .iter()
+            .enumerate()
+            .filter_map(|(order, tree)| tree.as_ref().map(|tree| (order, &tree.elem)))
+            .max_by(|first, second| first.1.cmp(second.1))
+            .map(|(order, _)| order)
+    }
+}
+
+impl<T> Default for BinomialHeap<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PriorityQueue<T> for BinomialHeap<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.len += 1;
+        self.add_trees(vec![Some(Box::new(Tree {
+            elem,
+            children: Vec::new(),
+        }))]);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        let order = self.max_order()?;
+        self.trees[order].as_ref().map(|tree| &tree.elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let order = self.max_order()?;
+        let tree = self.trees[order].take()?;
+        while let Some(None) = self.trees.last() {
+            self.trees.pop();
+        }
+        self.len -= 1;
+        // children of a tree of order k are trees of orders 0..k, a valid forest on their own
+        self.add_trees(tree.children.into_iter().map(Some).collect());
+
+        Some(tree.elem)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::binomial_heap::BinomialHeap;
+    use crate::collection::priority_queue::PriorityQueue;
+    use crate::collection::test_utils::{assert_same_as_binary_heap, drain};
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    #[test]
+    fn meld() {
+        let mut first = BinomialHeap::from(vec![3, 2, 3, 4]);
+        let second = BinomialHeap::from(vec![1, 6, 9, 0, 5]);
+
+        first.meld(second);
+
+        NumericAssert::assert_that(actual(first.len()))
+            .is_equal()
+            .to(expected(9));
+        ListAssert::assert_that(actual_vec(drain(&mut first)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 5, 4, 3, 3, 2, 1, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn trees_follow_binary_representation_of_size() {
+        let heap = BinomialHeap::from((0..13).collect());
+
+        let orders: Vec<usize> = heap
+            .trees
+            .iter()
+            .enumerate()
+            .filter(|(_, tree)| tree.is_some())
+            .map(|(order, _)| order)
+            .collect();
+
+        ListAssert::assert_that(actual_vec(orders))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 2, 3]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_binary_heap() {
+        assert_same_as_binary_heap(BinomialHeap::new, BinomialHeap::meld);
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap: BinomialHeap<i32> = BinomialHeap::new();
+
+        OptionAssert::assert_that(heap.peek().copied()).is_none();
+        OptionAssert::assert_that(heap.pop()).is_none();
+    }
+}
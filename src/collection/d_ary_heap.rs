@@ -0,0 +1,230 @@
+use crate::collection::priority_queue::PriorityQueue;
+use crate::operations::SortOperations;
+use std::cmp::Ordering;
+
+/// Heap ordered complete `D`-ary tree in a vector, gives out the greatest element first.
+///
+/// Children of the element at `index` are at `D * index + 1 ..= D * index + D`.
+/// Wider nodes make the tree shallower and keep the children of a node next to each other
+/// in memory: insert gets cheaper, pop compares more elements per level.
+/// A heap with `D` less than 2 does not compile.
+pub struct DaryHeap<T, const D: usize> {
+    elements: Vec<T>,
+}
+
+impl<T, const D: usize> DaryHeap<T, D>
+where
+    T: Ord,
+{
+    pub fn new() -> DaryHeap<T, D> {
+        Self::from(Vec::new())
+    }
+
+    pub fn from(mut elements: Vec<T>) -> DaryHeap<T, D> {
+        const { assert!(D >= 2, "a heap needs at least 2 children per node") };
+        heapify(&mut elements, D, &mut T::cmp);
+
+        DaryHeap { elements }
+    }
+
+    /// Removes the greatest element, `None` if the heap is empty
+    pub fn delete_max(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+        let removed = self.elements.swap_remove(0);
+        let len = self.elements.len();
+        sink_till(&mut self.elements, 0, len, D, &mut T::cmp);
+
+        Some(removed)
+    }
+}
+
+impl<T, const D: usize> Default for DaryHeap<T, D>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const D: usize> PriorityQueue<T> for DaryHeap<T, D>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.elements.push(elem);
+        let last_index = self.elements.len() - 1;
+        swim(&mut self.elements, last_index, D, &mut T::cmp);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.delete_max()
+    }
+
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+/// Heap sort in place with `arity` children per node, every comparison and swap
+/// goes through `operations`
+///
+/// # Panics
+///
+/// If `arity` is less than 2
+pub(crate) fn sort_slice_with_operations<T, O>(elements: &mut [T], arity: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    assert!(
+        arity >= 2,
+        "a heap needs at least 2 children per node, got {arity}"
+    );
+    heapify(elements, arity, operations);
+    let mut count = elements.len();
+    while count > 1 {
+        count -= 1;
+        operations.swap(elements, 0, count);
+        sink_till(elements, 0, count, arity, operations);
+    }
+}
+
+fn heapify<T, O>(elements: &mut [T], arity: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    let len = elements.len();
+    if len < 2 {
+        return;
+    }
+    // the parent of the last element is the last one with children
+    for index in (0..=(len - 2) / arity).rev() {
+        sink_till(elements, index, len, arity, operations);
+    }
+}
+
+fn swim<T, O>(elements: &mut [T], mut index: usize, arity: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    while index > 0 {
+        let parent = (index - 1) / arity;
+        if operations.compare(&elements[index], &elements[parent]) != Ordering::Greater {
+            return;
+        }
+        operations.swap(elements, index, parent);
+        index = parent;
+    }
+}
+
+/// Sinks the element at `index`, only elements before `till` (exclusive) belong to the heap
+fn sink_till<T, O>(
+    elements: &mut [T],
+    mut index: usize,
+    till: usize,
+    arity: usize,
+    operations: &mut O,
+) where
+    O: SortOperations<T>,
+{
+    loop {
+        let first_child = arity * index + 1;
+        if first_child >= till {
+            return;
+        }
+        let last_child = (first_child + arity).min(till);
+        let mut greatest = first_child;
+        for child in first_child + 1..last_child {
+            if operations
+                .compare(&elements[child], &elements[greatest])
+                .is_gt()
+            {
+                greatest = child;
+            }
+        }
+        if !operations
+            .compare(&elements[greatest], &elements[index])
+            .is_gt()
+        {
+            return;
+        }
+        operations.swap(elements, index, greatest);
+        index = greatest;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::d_ary_heap::DaryHeap;
+    use crate::collection::priority_queue::{MaxHeap, PriorityQueue};
+    use crate::collection::test_utils::drain;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn basics() {
+        let mut heap: DaryHeap<i32, 3> = DaryHeap::from(vec![3, 2, 3, 4]);
+        for value in [1, 6, 9, 0] {
+            heap.insert(value);
+        }
+
+        NumericAssert::assert_that(actual(heap.peek().copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(9));
+        ListAssert::assert_that(actual_vec(drain(&mut heap)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 4, 3, 3, 2, 1, 0]))
+            .in_order();
+    }
+
+    /// Fills a `DaryHeap<i32, D>` and a [`MaxHeap`] with the same random elements,
+    /// checks that both give out the same elements
+    fn assert_drains_like_max_heap<const D: usize>() {
+        let mut rng = StdRng::seed_from_u64(D as u64);
+        let initial: Vec<i32> = (0..500).map(|_| rng.gen_range(0..100)).collect();
+        let mut heap: DaryHeap<i32, D> = DaryHeap::from(initial.clone());
+        let mut max_heap = MaxHeap::new();
+        for value in initial {
+            max_heap.insert(value);
+        }
+        for _ in 0..1000 {
+            let value = rng.gen_range(0..100);
+            heap.insert(value);
+            max_heap.insert(value);
+            if rng.gen_bool(0.3) {
+                BooleanAssert::assert_that(heap.pop() == max_heap.pop()).is_true();
+            }
+        }
+
+        ListAssert::assert_that(actual_vec(drain(&mut heap)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(drain(&mut max_heap)))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_binary_heap() {
+        assert_drains_like_max_heap::<2>();
+        assert_drains_like_max_heap::<4>();
+        assert_drains_like_max_heap::<8>();
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap: DaryHeap<i32, 4> = DaryHeap::new();
+
+        OptionAssert::assert_that(heap.peek().copied()).is_none();
+        OptionAssert::assert_that(heap.pop()).is_none();
+    }
+}
@@ -147,6 +147,7 @@ mod test {
     use super::List;
 
     #[cfg(test)]
+    #[allow(clippy::module_inception)]
     mod test {
         use super::List;
         #[test]
@@ -202,7 +203,9 @@ mod test {
 
             assert!(list.peek() == Some(&3));
             list.push(6);
-            list.peek_mut().map(|x| *x *= 10);
+            if let Some(x) = list.peek_mut() {
+                *x *= 10;
+            }
             assert!(list.peek() == Some(&30));
             assert!(list.pop() == Some(30));
 
@@ -218,7 +221,9 @@ mod test {
             assert_eq!(iter.next(), None);
 
             assert!(list.pop() == Some(400));
-            list.peek_mut().map(|x| *x *= 10);
+            if let Some(x) = list.peek_mut() {
+                *x *= 10;
+            }
             assert!(list.peek() == Some(&5000));
             list.push(7);
 
@@ -0,0 +1,196 @@
+use crate::collection::priority_queue::PriorityQueue;
+use std::collections::LinkedList;
+use std::mem;
+
+/// Mergeable priority queue which gives out the greatest element first.
+///
+/// A lazy forest of heap ordered trees: insert and meld only add trees to the root list
+/// in constant time, pop links the roots of equal degree together and takes amortized
+/// logarithmic time. Elements can't be addressed after they are inserted,
+/// so there is no increase key and trees are never cut.
+pub struct FibonacciHeap<T> {
+    /// Tree with the greatest root, kept out of the root list
+    max: Option<Box<Node<T>>>,
+    roots: LinkedList<Box<Node<T>>>,
+    len: usize,
+}
+
+struct Node<T> {
+    elem: T,
+    children: LinkedList<Box<Node<T>>>,
+}
+
+impl<T> FibonacciHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> FibonacciHeap<T> {
+        FibonacciHeap {
+            max: None,
+            roots: LinkedList::new(),
+            len: 0,
+        }
+    }
+
+    pub fn from(elements: Vec<T>) -> FibonacciHeap<T> {
+        let mut heap = FibonacciHeap::new();
+        for elem in elements {
+            heap.insert(elem);
+        }
+
+        heap
+    }
+
+    /// Moves all elements of `other` into this heap in constant time
+    pub fn meld(&mut self, mut other: FibonacciHeap<T>) {
+        self.len += other.len;
+        self.roots.append(&mut other.roots);
+        if let Some(max) = other.max {
+            self.add_root(max);
+        }
+    }
+
+    fn add_root(&mut self, tree: Box<Node<T>>) {
+        match self.max.take() {
+            Some(max) if tree.elem > max.elem => {
+                self.roots.push_back(max);
+                self.max = Some(tree);
+            }
+            Some(max) => {
+                self.roots.push_back(tree);
+                self.max = Some(max);
+            }
+            None => self.max = Some(tree),
+        }
+    }
+
+    /// Links roots of the same degree until all degrees differ, then finds the new maximum
+    fn consolidate(&mut self) {
+        let mut by_degree: Vec<Option<Box<Node<T>>>> = Vec::new();
+        while let Some(mut tree) = self.roots.pop_front() {
+            loop {
+                let degree = tree.children.len();
+                if degree >= by_degree.len() {
+                    by_degree.resize_with(degree + 1, || None);
+                }
+                match by_degree[degree].take() {
+                    Some(other) => tree = Self::link(tree, other),
+                    None => {
+                        by_degree[degree] = Some(tree);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for tree in by_degree.into_iter().flatten() {
+            self.add_root(tree);
+        }
+    }
+
+    /// Makes the tree with the lesser root a child of the other one
+    fn link(mut first: Box<Node<T>>, mut second: Box<Node<T>>) -> Box<Node<T>> {
+        if second.elem > first.elem {
+            mem::swap(&mut first, &mut second);
+        }
+        first.children.push_back(second);
+
+        first
+    }
+}
+
+impl<T> Default for FibonacciHeap<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PriorityQueue<T> for FibonacciHeap<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.len += 1;
+        self.add_root(Box::new(Node {
+            elem,
+            children: LinkedList::new(),
+        }));
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.max.as_ref().map(|max| &max.elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let max = self.max.take()?;
+        let Node { elem, mut children } = *max;
+        self.roots.append(&mut children);
+        self.consolidate();
+        self.len -= 1;
+
+        Some(elem)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::fibonacci_heap::FibonacciHeap;
+    use crate::collection::priority_queue::PriorityQueue;
+    use crate::collection::test_utils::{assert_same_as_binary_heap, drain};
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    #[test]
+    fn meld() {
+        let mut first = FibonacciHeap::from(vec![3, 2, 3, 4]);
+        let second = FibonacciHeap::from(vec![1, 6, 9, 0, 5]);
+
+        first.meld(second);
+
+        NumericAssert::assert_that(actual(first.len()))
+            .is_equal()
+            .to(expected(9));
+        ListAssert::assert_that(actual_vec(drain(&mut first)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 5, 4, 3, 3, 2, 1, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn pop_leaves_distinct_degrees() {
+        let mut heap = FibonacciHeap::from((0..100).collect());
+
+        heap.pop();
+
+        // 99 elements left: trees of 64, 32, 2 and 1 elements
+        let mut degrees: Vec<usize> = heap.roots.iter().map(|root| root.children.len()).collect();
+        degrees.extend(heap.max.iter().map(|max| max.children.len()));
+        degrees.sort();
+        ListAssert::assert_that(actual_vec(degrees))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 5, 6]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_binary_heap() {
+        assert_same_as_binary_heap(FibonacciHeap::new, FibonacciHeap::meld);
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap: FibonacciHeap<i32> = FibonacciHeap::new();
+
+        OptionAssert::assert_that(heap.peek().copied()).is_none();
+        OptionAssert::assert_that(heap.pop()).is_none();
+    }
+}
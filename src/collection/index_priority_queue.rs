@@ -0,0 +1,398 @@
+use crate::collection::simple_binary_heap::SimpleBinaryHeap;
+use crate::operations::SortOperations;
+use crate::AlgoError;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Which of two keys comes out of an [`IndexPriorityQueue`] first
+pub trait KeyOrder {
+    /// `Greater` when `first` has the higher priority
+    fn priority<K: Ord>(first: &K, second: &K) -> Ordering;
+}
+
+/// The least key has the highest priority
+pub struct MinFirst;
+
+/// The greatest key has the highest priority
+pub struct MaxFirst;
+
+impl KeyOrder for MinFirst {
+    fn priority<K: Ord>(first: &K, second: &K) -> Ordering {
+        second.cmp(first)
+    }
+}
+
+impl KeyOrder for MaxFirst {
+    fn priority<K: Ord>(first: &K, second: &K) -> Ordering {
+        first.cmp(second)
+    }
+}
+
+pub type IndexMinPQ<K> = IndexPriorityQueue<K, MinFirst>;
+
+pub type IndexMaxPQ<K> = IndexPriorityQueue<K, MaxFirst>;
+
+/// Priority queue of keys associated with indices from 0 to the capacity (exclusive).
+///
+/// The key of any index can be changed or deleted in logarithmic time, which Dijkstra's and
+/// Prim's algorithms need. The heap holds indices only, it is kept in order by the sink and swim
+/// of [`SimpleBinaryHeap`], which report every swap so the position of each index is tracked.
+///
+/// Using an index out of the capacity panics, as indexing a slice does.
+pub struct IndexPriorityQueue<K, O> {
+    heap: Vec<usize>,
+    /// position of every index in `heap`, `None` for indices which are not in the queue
+    positions: Vec<Option<usize>>,
+    keys: Vec<Option<K>>,
+    order: PhantomData<O>,
+}
+
+impl<K, O> IndexPriorityQueue<K, O>
+where
+    K: Ord,
+    O: KeyOrder,
+{
+    /// Empty queue for indices from 0 to `capacity` (exclusive)
+    pub fn new(capacity: usize) -> IndexPriorityQueue<K, O> {
+        IndexPriorityQueue {
+            heap: Vec::with_capacity(capacity),
+            positions: vec![None; capacity],
+            keys: (0..capacity).map(|_| None).collect(),
+            order: PhantomData,
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, key: K) -> Result<(), AlgoError> {
+        if self.contains(index) {
+            return Err(AlgoError::element_already_exist("index", &index));
+        }
+        self.keys[index] = Some(key);
+        self.positions[index] = Some(self.heap.len());
+        self.heap.push(index);
+        self.swim(self.heap.len() - 1);
+
+        Ok(())
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.positions[index].is_some()
+    }
+
+    pub fn key_of(&self, index: usize) -> Option<&K> {
+        self.keys[index].as_ref()
+    }
+
+    /// Index with the highest priority together with its key, `None` if the queue is empty
+    pub fn peek(&self) -> Option<(usize, &K)> {
+        let index = *self.heap.first()?;
+
+        self.key_of(index).map(|key| (index, key))
+    }
+
+    /// Removes the index with the highest priority, `None` if the queue is empty
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        let index = *self.heap.first()?;
+
+        self.delete(index).ok().map(|key| (index, key))
+    }
+
+    /// Sets the key of `index` to any value
+    pub fn change_key(&mut self, index: usize, key: K) -> Result<(), AlgoError> {
+        let position = self.position_of(index)?;
+        self.keys[index] = Some(key);
+        self.restore(position);
+
+        Ok(())
+    }
+
+    /// Sets the key of `index` to a value which is not greater than the current one.
+    /// Returns [`AlgoError::InvalidArgument`] without changing anything when `key` is greater
+    pub fn decrease_key(&mut self, index: usize, key: K) -> Result<(), AlgoError> {
+        let position = self.position_of(index)?;
+        if self.keys[index]
+            .as_ref()
+            .is_some_and(|current| key > *current)
+        {
+            return Err(AlgoError::invalid_argument(
+                "index",
+                &index,
+                "got a greater key in decrease_key",
+            ));
+        }
+        self.keys[index] = Some(key);
+        self.restore(position);
+
+        Ok(())
+    }
+
+    /// Sets the key of `index` to a value which is not less than the current one.
+    /// Returns [`AlgoError::InvalidArgument`] without changing anything when `key` is smaller
+    pub fn increase_key(&mut self, index: usize, key: K) -> Result<(), AlgoError> {
+        let position = self.position_of(index)?;
+        if self.keys[index]
+            .as_ref()
+            .is_some_and(|current| key < *current)
+        {
+            return Err(AlgoError::invalid_argument(
+                "index",
+                &index,
+                "got a smaller key in increase_key",
+            ));
+        }
+        self.keys[index] = Some(key);
+        self.restore(position);
+
+        Ok(())
+    }
+
+    /// Removes `index` from the queue and returns its key
+    pub fn delete(&mut self, index: usize) -> Result<K, AlgoError> {
+        let position = self.position_of(index)?;
+        let last = self.heap.len() - 1;
+        let (heap, mut operations) = self.heap_and_operations();
+        operations.swap(heap, position, last);
+        self.heap.pop();
+        self.positions[index] = None;
+        if position < self.heap.len() {
+            self.restore(position);
+        }
+
+        Ok(self.keys[index]
+            .take()
+            .expect("every index in the heap has a key"))
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn position_of(&self, index: usize) -> Result<usize, AlgoError> {
+        self.positions[index].ok_or_else(|| AlgoError::missing_element("index", &index))
+    }
+
+    /// Moves the index at `position` whichever way its key changed
+    fn restore(&mut self, position: usize) {
+        self.swim(position);
+        self.sink(position);
+    }
+
+    fn swim(&mut self, position: usize) {
+        let (heap, mut operations) = self.heap_and_operations();
+        SimpleBinaryHeap::pop_up(heap, position, &mut operations);
+    }
+
+    fn sink(&mut self, position: usize) {
+        let (heap, mut operations) = self.heap_and_operations();
+        let len = heap.len();
+        SimpleBinaryHeap::sink_till(heap, position, len, &mut operations);
+    }
+
+    fn heap_and_operations(&mut self) -> (&mut [usize], KeyOperations<'_, K, O>) {
+        let operations = KeyOperations {
+            keys: &self.keys,
+            positions: &mut self.positions,
+            order: PhantomData,
+        };
+
+        (&mut self.heap, operations)
+    }
+}
+
+/// Compares the indices of the heap by their keys and keeps their positions up to date
+struct KeyOperations<'a, K, O> {
+    keys: &'a [Option<K>],
+    positions: &'a mut [Option<usize>],
+    order: PhantomData<O>,
+}
+
+impl<'a, K, O> SortOperations<usize> for KeyOperations<'a, K, O>
+where
+    K: Ord,
+    O: KeyOrder,
+{
+    fn compare(&mut self, first: &usize, second: &usize) -> Ordering {
+        match (&self.keys[*first], &self.keys[*second]) {
+            (Some(first), Some(second)) => O::priority(first, second),
+            _ => unreachable!("every index in the heap has a key"),
+        }
+    }
+
+    fn swap(&mut self, heap: &mut [usize], first: usize, second: usize) {
+        heap.swap(first, second);
+        self.positions[heap[first]] = Some(first);
+        self.positions[heap[second]] = Some(second);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::index_priority_queue::{IndexMaxPQ, IndexMinPQ};
+    use crate::AlgoError;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn drain_min(queue: &mut IndexMinPQ<u32>) -> Vec<(usize, u32)> {
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop() {
+            popped.push(entry);
+        }
+
+        popped
+    }
+
+    #[test]
+    fn basics_min() {
+        let mut queue = IndexMinPQ::new(10);
+        for (index, key) in [(3, 30), (1, 10), (7, 70), (0, 40), (9, 20)] {
+            BooleanAssert::assert_that(queue.insert(index, key).is_ok()).is_true();
+        }
+
+        NumericAssert::assert_that(actual(queue.len()))
+            .is_equal()
+            .to(expected(5));
+        BooleanAssert::assert_that(queue.peek() == Some((1, &10))).is_true();
+        let indices: Vec<usize> = drain_min(&mut queue)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        ListAssert::assert_that(actual_vec(indices))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![1, 9, 3, 0, 7]))
+            .in_order();
+        BooleanAssert::assert_that(queue.is_empty()).is_true();
+    }
+
+    #[test]
+    fn basics_max() {
+        let mut queue = IndexMaxPQ::new(5);
+        for (index, key) in [(0, "b"), (1, "d"), (2, "a"), (3, "c")] {
+            BooleanAssert::assert_that(queue.insert(index, key).is_ok()).is_true();
+        }
+
+        let mut indices = Vec::new();
+        while let Some((index, _)) = queue.pop() {
+            indices.push(index);
+        }
+
+        ListAssert::assert_that(actual_vec(indices))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![1, 3, 0, 2]))
+            .in_order();
+    }
+
+    #[test]
+    fn insert_existing_index() {
+        let mut queue = IndexMinPQ::new(3);
+        BooleanAssert::assert_that(queue.insert(1, 5).is_ok()).is_true();
+
+        let result = queue.insert(1, 3);
+
+        BooleanAssert::assert_that(result.is_err()).is_true();
+        BooleanAssert::assert_that(queue.key_of(1) == Some(&5)).is_true();
+    }
+
+    #[test]
+    fn change_and_decrease_key() {
+        let mut queue = IndexMinPQ::new(4);
+        for (index, key) in [(0, 10), (1, 20), (2, 30), (3, 40)] {
+            BooleanAssert::assert_that(queue.insert(index, key).is_ok()).is_true();
+        }
+
+        BooleanAssert::assert_that(queue.decrease_key(3, 5).is_ok()).is_true();
+        BooleanAssert::assert_that(queue.peek() == Some((3, &5))).is_true();
+        BooleanAssert::assert_that(queue.change_key(3, 50).is_ok()).is_true();
+        BooleanAssert::assert_that(queue.increase_key(0, 25).is_ok()).is_true();
+
+        BooleanAssert::assert_that(
+            drain_min(&mut queue) == vec![(1, 20), (0, 25), (2, 30), (3, 50)],
+        )
+        .is_true();
+    }
+
+    #[test]
+    fn key_changed_the_wrong_way() {
+        let mut queue = IndexMinPQ::new(2);
+        BooleanAssert::assert_that(queue.insert(0, 10).is_ok()).is_true();
+
+        let decreased = queue.decrease_key(0, 11);
+        let increased = queue.increase_key(0, 9);
+
+        BooleanAssert::assert_that(matches!(decreased, Err(AlgoError::InvalidArgument(_))))
+            .is_true();
+        BooleanAssert::assert_that(matches!(increased, Err(AlgoError::InvalidArgument(_))))
+            .is_true();
+        BooleanAssert::assert_that(queue.key_of(0) == Some(&10)).is_true();
+    }
+
+    #[test]
+    fn delete() {
+        let mut queue = IndexMinPQ::new(6);
+        for (index, key) in [(0, 10), (1, 20), (2, 30), (3, 40), (4, 50), (5, 60)] {
+            BooleanAssert::assert_that(queue.insert(index, key).is_ok()).is_true();
+        }
+
+        let deleted = queue.delete(1).unwrap_or(0);
+
+        NumericAssert::assert_that(actual(deleted))
+            .is_equal()
+            .to(expected(20));
+        BooleanAssert::assert_that(queue.contains(1)).is_false();
+        BooleanAssert::assert_that(queue.delete(1).is_err()).is_true();
+        BooleanAssert::assert_that(queue.change_key(1, 1).is_err()).is_true();
+        OptionAssert::assert_that(queue.key_of(1).copied()).is_none();
+        BooleanAssert::assert_that(
+            drain_min(&mut queue) == vec![(0, 10), (2, 30), (3, 40), (4, 50), (5, 60)],
+        )
+        .is_true();
+    }
+
+    #[test]
+    fn same_as_linear_scan() {
+        let capacity = 50;
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = IndexMinPQ::new(capacity);
+        let mut keys: Vec<Option<u32>> = vec![None; capacity];
+        for _ in 0..5000 {
+            let index = rng.gen_range(0..capacity);
+            let key = rng.gen_range(0..100);
+            match rng.gen_range(0..4) {
+                0 => {
+                    let inserted = queue.insert(index, key).is_ok();
+                    BooleanAssert::assert_that(inserted == keys[index].is_none()).is_true();
+                    keys[index].get_or_insert(key);
+                }
+                1 => {
+                    let changed = queue.change_key(index, key).is_ok();
+                    BooleanAssert::assert_that(changed == keys[index].is_some()).is_true();
+                    if changed {
+                        keys[index] = Some(key);
+                    }
+                }
+                2 => {
+                    let deleted = queue.delete(index).ok();
+                    BooleanAssert::assert_that(deleted == keys[index].take()).is_true();
+                }
+                _ => {
+                    let min = keys.iter().flatten().min().copied();
+                    let popped = queue.pop();
+                    BooleanAssert::assert_that(popped.map(|(_, key)| key) == min).is_true();
+                    if let Some((index, _)) = popped {
+                        keys[index] = None;
+                    }
+                }
+            }
+            NumericAssert::assert_that(actual(queue.len()))
+                .is_equal()
+                .to(expected(keys.iter().flatten().count()));
+        }
+    }
+}
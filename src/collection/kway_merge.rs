@@ -0,0 +1,164 @@
+use crate::collection::priority_queue::{MinHeap, PriorityQueue};
+
+/// Iterator over the elements of several sorted iterators in sorted order,
+/// made by [`kway_merge`]
+pub struct KWayMerge<I>
+where
+    I: Iterator,
+{
+    sources: Vec<I>,
+    /// Next element of every source which is not exhausted yet
+    heads: MinHeap<Head<I::Item>>,
+}
+
+/// Ordered by the element, then by the source so equal elements keep the order of the sources
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Head<T> {
+    elem: T,
+    source: usize,
+}
+
+/// Lazily merges already sorted `sources`: only the next element of every source
+/// is held at a time, each element takes time proportional to log N for N sources.
+///
+/// Equal elements come out in the order of their sources, so the merge is stable.
+pub fn kway_merge<S, I>(sources: S) -> KWayMerge<I::IntoIter>
+where
+    S: IntoIterator<Item = I>,
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    let mut sources: Vec<I::IntoIter> = sources.into_iter().map(I::into_iter).collect();
+    let mut heads = MinHeap::new();
+    for (source, iterator) in sources.iter_mut().enumerate() {
+        if let Some(elem) = iterator.next() {
+            heads.insert(Head { elem, source });
+        }
+    }
+
+    KWayMerge { sources, heads }
+}
+
+impl<I> Iterator for KWayMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Head { elem, source } = self.heads.pop()?;
+        if let Some(next) = self.sources[source].next() {
+            self.heads.insert(Head { elem: next, source });
+        }
+
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sources.iter().fold(
+            (self.heads.len(), Some(self.heads.len())),
+            |(low, high), source| {
+                let (source_low, source_high) = source.size_hint();
+                (
+                    low.saturating_add(source_low),
+                    high.zip(source_high)
+                        .and_then(|(high, source_high)| high.checked_add(source_high)),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::kway_merge::kway_merge;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn basics() {
+        let sources = vec![vec![1, 4, 7], vec![], vec![2, 3, 9, 10], vec![0]];
+
+        let merged: Vec<i32> = kway_merge(sources).collect();
+
+        ListAssert::assert_that(actual_vec(merged))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 4, 7, 9, 10]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_sorting() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sources: Vec<Vec<u32>> = (0..50)
+            .map(|_| {
+                let len = rng.gen_range(0..200);
+                let mut source: Vec<u32> = (0..len).map(|_| rng.gen_range(0..1000)).collect();
+                source.sort();
+                source
+            })
+            .collect();
+        let mut expected_elements: Vec<u32> = sources.concat();
+        expected_elements.sort();
+
+        let merge = kway_merge(sources);
+        NumericAssert::assert_that(actual(merge.size_hint().0))
+            .is_equal()
+            .to(expected(expected_elements.len()));
+
+        ListAssert::assert_that(actual_vec(merge.collect()))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected_elements))
+            .in_order();
+    }
+
+    #[test]
+    fn stable() {
+        /// Compared by the key only
+        struct Record(u8, usize);
+        impl PartialEq for Record {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Record {}
+        impl PartialOrd for Record {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Record {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+        let sources = (0..5).map(|source| (0..3).map(move |key| Record(key, source)));
+
+        let sources_in_order: Vec<(u8, usize)> = kway_merge(sources)
+            .map(|Record(key, source)| (key, source))
+            .collect();
+
+        let expected_order: Vec<(u8, usize)> = (0..3)
+            .flat_map(|key| (0..5).map(move |source| (key, source)))
+            .collect();
+        BooleanAssert::assert_that(sources_in_order == expected_order).is_true();
+    }
+
+    #[test]
+    fn lazy_over_infinite_sources() {
+        let multiples = (2..5).map(|factor| (1..).map(move |n| n * factor));
+
+        let merged: Vec<u64> = kway_merge(multiples).take(8).collect();
+
+        ListAssert::assert_that(actual_vec(merged))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![2, 3, 4, 4, 6, 6, 8, 8]))
+            .in_order();
+    }
+}
@@ -0,0 +1,277 @@
+use std::cmp::Ordering;
+
+/// Double-ended priority queue: both the least and the greatest element are found
+/// in constant time and removed in logarithmic time.
+///
+/// Levels of the heap alternate: every element on an even level (the root is on level 0)
+/// is not greater than anything below it, every element on an odd level is not less than
+/// anything below it. So the least element is the root and the greatest one is one of its children.
+pub struct MinMaxHeap<T> {
+    elements: Vec<T>,
+}
+
+impl<T> MinMaxHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> MinMaxHeap<T> {
+        MinMaxHeap {
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn from(elements: Vec<T>) -> MinMaxHeap<T> {
+        let mut heap = MinMaxHeap { elements };
+        for index in (0..heap.elements.len() / 2).rev() {
+            heap.trickle_down(index);
+        }
+
+        heap
+    }
+
+    pub fn insert(&mut self, elem: T) {
+        self.elements.push(elem);
+        self.bubble_up(self.elements.len() - 1);
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.elements[index])
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+
+        Some(self.remove(0))
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+
+        Some(self.remove(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.elements.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if self.elements[2] > self.elements[1] => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    /// Removes the root or one of its children, the last element takes its place and sinks
+    fn remove(&mut self, index: usize) -> T {
+        let removed = self.elements.swap_remove(index);
+        if index < self.elements.len() {
+            self.trickle_down(index);
+        }
+
+        removed
+    }
+
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+        let parent = (index - 1) / 2;
+        let towards = Self::order_of_level(index);
+        if self.elements[parent].cmp(&self.elements[index]) == towards {
+            // too extreme for its level, so it belongs to the levels of the parent
+            self.elements.swap(index, parent);
+            self.bubble_up_by(parent, towards.reverse());
+        } else {
+            self.bubble_up_by(index, towards);
+        }
+    }
+
+    /// Moves the element up through the levels of its kind while it is more `towards` than
+    /// its grandparent
+    fn bubble_up_by(&mut self, mut index: usize, towards: Ordering) {
+        while index > 2 {
+            let grandparent = (index - 3) / 4;
+            if self.elements[index].cmp(&self.elements[grandparent]) != towards {
+                break;
+            }
+            self.elements.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        let towards = Self::order_of_level(index);
+        loop {
+            let Some(extreme) = self.most_towards_below(index, towards) else {
+                return;
+            };
+            if self.elements[extreme].cmp(&self.elements[index]) != towards {
+                return;
+            }
+            self.elements.swap(extreme, index);
+            if extreme <= 2 * index + 2 {
+                // a child is on the other kind of level, nothing below it can be out of order
+                return;
+            }
+            let parent = (extreme - 1) / 2;
+            if self.elements[parent].cmp(&self.elements[extreme]) == towards {
+                self.elements.swap(parent, extreme);
+            }
+            index = extreme;
+        }
+    }
+
+    /// Index of the most `towards` element among children and grandchildren of `index`
+    fn most_towards_below(&self, index: usize, towards: Ordering) -> Option<usize> {
+        let first_child = 2 * index + 1;
+        let first_grandchild = 4 * index + 3;
+        (first_child..first_child + 2)
+            .chain(first_grandchild..first_grandchild + 4)
+            .filter(|below| *below < self.elements.len())
+            .reduce(|most, below| {
+                if self.elements[below].cmp(&self.elements[most]) == towards {
+                    below
+                } else {
+                    most
+                }
+            })
+    }
+
+    /// `Less` for levels holding minimums, `Greater` for levels holding maximums
+    fn order_of_level(index: usize) -> Ordering {
+        let level = usize::BITS - (index + 1).leading_zeros() - 1;
+        if level.is_multiple_of(2) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+impl<T> Default for MinMaxHeap<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::min_max_heap::MinMaxHeap;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn basics() {
+        let mut heap = MinMaxHeap::new();
+        for value in [3, 2, 3, 4, 1, 6, 9, 0] {
+            heap.insert(value);
+        }
+
+        NumericAssert::assert_that(actual(heap.peek_min().copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(0));
+        NumericAssert::assert_that(actual(heap.peek_max().copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(9));
+        NumericAssert::assert_that(actual(heap.len()))
+            .is_equal()
+            .to(expected(8));
+    }
+
+    #[test]
+    fn pop_from_both_ends() {
+        let mut heap = MinMaxHeap::from(vec![3, 2, 3, 4, 1, 6, 9, 0]);
+        let mut popped = Vec::new();
+
+        while let (Some(min), Some(max)) = (heap.pop_min(), heap.pop_max()) {
+            popped.push(min);
+            popped.push(max);
+        }
+
+        ListAssert::assert_that(actual_vec(popped))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 9, 1, 6, 2, 4, 3, 3]))
+            .in_order();
+        BooleanAssert::assert_that(heap.is_empty()).is_true();
+    }
+
+    #[test]
+    fn same_as_sorted_vec() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut heap = MinMaxHeap::new();
+        let mut sorted: Vec<u32> = Vec::new();
+        for _ in 0..5000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let value = rng.gen_range(0..100);
+                    heap.insert(value);
+                    let position = sorted.partition_point(|elem| *elem <= value);
+                    sorted.insert(position, value);
+                }
+                2 => {
+                    let expected_min = if sorted.is_empty() {
+                        None
+                    } else {
+                        Some(sorted.remove(0))
+                    };
+                    BooleanAssert::assert_that(heap.pop_min() == expected_min).is_true();
+                }
+                _ => {
+                    BooleanAssert::assert_that(heap.pop_max() == sorted.pop()).is_true();
+                }
+            }
+            BooleanAssert::assert_that(heap.peek_min() == sorted.first()).is_true();
+            BooleanAssert::assert_that(heap.peek_max() == sorted.last()).is_true();
+        }
+    }
+
+    #[test]
+    fn from_unordered() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let elements: Vec<u32> = (0..1000).map(|_| rng.gen_range(0..50)).collect();
+        let mut expected_elements = elements.clone();
+        expected_elements.sort();
+
+        let mut heap = MinMaxHeap::from(elements);
+        let mut popped = Vec::new();
+        while let Some(min) = heap.pop_min() {
+            popped.push(min);
+        }
+
+        ListAssert::assert_that(actual_vec(popped))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected_elements))
+            .in_order();
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap: MinMaxHeap<i32> = MinMaxHeap::new();
+
+        OptionAssert::assert_that(heap.peek_min().copied()).is_none();
+        OptionAssert::assert_that(heap.peek_max().copied()).is_none();
+        OptionAssert::assert_that(heap.pop_min()).is_none();
+        OptionAssert::assert_that(heap.pop_max()).is_none();
+    }
+}
@@ -1,13 +1,114 @@
+pub mod avl_tree;
+pub mod b_tree;
 pub mod binary_search_symbol_table;
 pub mod binary_tree;
+pub mod binomial_heap;
+pub mod d_ary_heap;
 pub mod dequeue;
 pub mod different_linked_list;
+pub mod fibonacci_heap;
+pub mod index_priority_queue;
+pub mod kway_merge;
+pub mod min_max_heap;
+pub mod ordered_symbol_table;
+pub mod pairing_heap;
+pub mod priority_queue;
+pub mod red_black_tree;
+mod search_tree;
 pub mod simple_binary_heap;
 pub mod simple_linked_list;
 pub mod stack;
+pub mod top_k;
 
 pub trait Collection {
     fn is_empty(&self) -> bool;
 
     fn size(&self) -> usize;
 }
+
+#[cfg(test)]
+mod test_utils {
+    use crate::collection::ordered_symbol_table::OrderedSymbolTable;
+    use crate::collection::priority_queue::{MaxHeap, PriorityQueue};
+    use easy_assert::bool_assertions::BooleanAssert;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Pops every element of the queue, in the order they come out
+    pub fn drain<Q>(queue: &mut Q) -> Vec<i32>
+    where
+        Q: PriorityQueue<i32>,
+    {
+        let mut elements = Vec::new();
+        while let Some(elem) = queue.pop() {
+            elements.push(elem);
+        }
+
+        elements
+    }
+
+    /// Runs the same random inserts, pops and melds on a queue made by `new`
+    /// and on a [`MaxHeap`], checks that both always give out the same elements
+    pub fn assert_same_as_binary_heap<Q>(new: impl Fn() -> Q, meld: impl Fn(&mut Q, Q))
+    where
+        Q: PriorityQueue<i32>,
+    {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut queue = new();
+        let mut binary_heap = MaxHeap::new();
+        for _ in 0..5000 {
+            match rng.gen_range(0..10) {
+                0..=4 => {
+                    let value = rng.gen_range(0..100);
+                    queue.insert(value);
+                    binary_heap.insert(value);
+                }
+                5..=8 => {
+                    BooleanAssert::assert_that(queue.pop() == binary_heap.pop()).is_true();
+                }
+                _ => {
+                    let mut other = new();
+                    for _ in 0..rng.gen_range(0..20) {
+                        let value = rng.gen_range(0..100);
+                        other.insert(value);
+                        binary_heap.insert(value);
+                    }
+                    meld(&mut queue, other);
+                }
+            }
+            BooleanAssert::assert_that(queue.peek() == PriorityQueue::peek(&binary_heap)).is_true();
+            BooleanAssert::assert_that(queue.len() == PriorityQueue::len(&binary_heap)).is_true();
+        }
+
+        BooleanAssert::assert_that(drain(&mut queue) == drain(&mut binary_heap)).is_true();
+    }
+
+    /// Puts ascending keys, then runs random puts and deletes on a table made by `new`,
+    /// calls `check` after every change so each tree can verify its own shape
+    pub fn assert_stays_balanced<T>(new: impl Fn() -> T, check: impl Fn(&T))
+    where
+        T: OrderedSymbolTable<u32, u32>,
+    {
+        let mut table = new();
+        for key in 0..1024 {
+            table.put(key, key);
+            check(&table);
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..3000 {
+            let key = rng.gen_range(0..1500);
+            match rng.gen_range(0..6) {
+                0 | 1 => table.delete(&key),
+                2 => {
+                    table.delete_min();
+                }
+                3 => {
+                    table.delete_max();
+                }
+                _ => table.put(key, key),
+            }
+            check(&table);
+        }
+    }
+}
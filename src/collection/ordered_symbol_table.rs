@@ -0,0 +1,342 @@
+use crate::collection::avl_tree::AvlTree;
+use crate::collection::b_tree::BTree;
+use crate::collection::binary_search_symbol_table::SymbolTable;
+use crate::collection::binary_tree::BinarySearchTree;
+use crate::collection::red_black_tree::RedBlackTree;
+use std::ops::RangeBounds;
+
+/// Symbol table which keeps its keys in order, so besides lookups it answers
+/// which keys are around a key, how many keys are below it and which keys are in a range
+pub trait OrderedSymbolTable<K, V>
+where
+    K: Ord,
+{
+    /// Adds the key or replaces its value
+    fn put(&mut self, key: K, value: V);
+
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Removes the key if it is in the table
+    fn delete(&mut self, key: &K);
+
+    fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of keys in the table
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The least key and its value
+    fn min(&self) -> Option<(&K, &V)>;
+
+    /// The greatest key and its value
+    fn max(&self) -> Option<(&K, &V)>;
+
+    /// The greatest key less than or equal to `key` and its value
+    fn floor(&self, key: &K) -> Option<(&K, &V)>;
+
+    /// The least key greater than or equal to `key` and its value
+    fn ceiling(&self, key: &K) -> Option<(&K, &V)>;
+
+    /// Number of keys less than `key`
+    fn rank(&self, key: &K) -> usize;
+
+    /// The key of rank `rank` and its value, `None` if there are not that many keys
+    fn select(&self, rank: usize) -> Option<(&K, &V)>;
+
+    /// Removes the least key, returns it with its value
+    fn delete_min(&mut self) -> Option<(K, V)>;
+
+    /// Removes the greatest key, returns it with its value
+    fn delete_max(&mut self) -> Option<(K, V)>;
+
+    /// Keys in `range` in ascending order
+    fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+    where
+        R: RangeBounds<K>;
+
+    /// Number of keys in `range`
+    fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>;
+}
+
+/// Implements [`OrderedSymbolTable`] with the inherent methods of the same names
+macro_rules! ordered_symbol_table {
+    ($($table:ident),*) => {
+        $(
+            impl<K, V> OrderedSymbolTable<K, V> for $table<K, V>
+            where
+                K: Ord,
+            {
+                fn put(&mut self, key: K, value: V) {
+                    $table::put(self, key, value)
+                }
+
+                fn get(&self, key: &K) -> Option<&V> {
+                    $table::get(self, key)
+                }
+
+                fn delete(&mut self, key: &K) {
+                    $table::delete(self, key)
+                }
+
+                fn contains(&self, key: &K) -> bool {
+                    $table::contains(self, key)
+                }
+
+                fn len(&self) -> usize {
+                    $table::len(self)
+                }
+
+                fn is_empty(&self) -> bool {
+                    $table::is_empty(self)
+                }
+
+                fn min(&self) -> Option<(&K, &V)> {
+                    $table::min(self)
+                }
+
+                fn max(&self) -> Option<(&K, &V)> {
+                    $table::max(self)
+                }
+
+                fn floor(&self, key: &K) -> Option<(&K, &V)> {
+                    $table::floor(self, key)
+                }
+
+                fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+                    $table::ceiling(self, key)
+                }
+
+                fn rank(&self, key: &K) -> usize {
+                    $table::rank(self, key)
+                }
+
+                fn select(&self, rank: usize) -> Option<(&K, &V)> {
+                    $table::select(self, rank)
+                }
+
+                fn delete_min(&mut self) -> Option<(K, V)> {
+                    $table::delete_min(self)
+                }
+
+                fn delete_max(&mut self) -> Option<(K, V)> {
+                    $table::delete_max(self)
+                }
+
+                fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+                where
+                    R: RangeBounds<K>,
+                {
+                    $table::keys_in_range(self, range)
+                }
+
+                fn size_in_range<R>(&self, range: R) -> usize
+                where
+                    R: RangeBounds<K>,
+                {
+                    $table::size_in_range(self, range)
+                }
+            }
+        )*
+    };
+}
+
+ordered_symbol_table!(SymbolTable, BinarySearchTree, RedBlackTree, AvlTree, BTree);
+
+#[cfg(test)]
+mod test {
+    use crate::collection::avl_tree::AvlTree;
+    use crate::collection::b_tree::BTree;
+    use crate::collection::binary_search_symbol_table::SymbolTable;
+    use crate::collection::binary_tree::BinarySearchTree;
+    use crate::collection::ordered_symbol_table::OrderedSymbolTable;
+    use crate::collection::red_black_tree::RedBlackTree;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeMap;
+
+    /// Every implementation has to pass all of these
+    fn conformance<T>(new: impl Fn() -> T)
+    where
+        T: OrderedSymbolTable<u32, u32>,
+    {
+        empty(new());
+        ordered_operations(new());
+        same_as_btree_map(new());
+    }
+
+    fn empty<T>(mut table: T)
+    where
+        T: OrderedSymbolTable<u32, u32>,
+    {
+        BooleanAssert::assert_that(table.is_empty()).is_true();
+        BooleanAssert::assert_that(table.get(&1).is_none()).is_true();
+        BooleanAssert::assert_that(table.min().is_none()).is_true();
+        BooleanAssert::assert_that(table.max().is_none()).is_true();
+        BooleanAssert::assert_that(table.floor(&1).is_none()).is_true();
+        BooleanAssert::assert_that(table.ceiling(&1).is_none()).is_true();
+        BooleanAssert::assert_that(table.select(0).is_none()).is_true();
+        BooleanAssert::assert_that(table.delete_min().is_none()).is_true();
+        BooleanAssert::assert_that(table.delete_max().is_none()).is_true();
+        NumericAssert::assert_that(actual(table.rank(&1)))
+            .is_equal()
+            .to(expected(0));
+        NumericAssert::assert_that(actual(table.size_in_range(..)))
+            .is_equal()
+            .to(expected(0));
+        table.delete(&1);
+        BooleanAssert::assert_that(table.is_empty()).is_true();
+    }
+
+    fn copied(keys: Vec<&u32>) -> Vec<u32> {
+        keys.into_iter().copied().collect()
+    }
+
+    fn ordered_operations<T>(mut table: T)
+    where
+        T: OrderedSymbolTable<u32, u32>,
+    {
+        for key in [50, 20, 80, 10, 30, 70, 90, 20] {
+            table.put(key, key * 10);
+        }
+
+        NumericAssert::assert_that(actual(table.len()))
+            .is_equal()
+            .to(expected(7));
+        BooleanAssert::assert_that(table.min() == Some((&10, &100))).is_true();
+        BooleanAssert::assert_that(table.max() == Some((&90, &900))).is_true();
+        BooleanAssert::assert_that(table.floor(&65) == Some((&50, &500))).is_true();
+        BooleanAssert::assert_that(table.ceiling(&65) == Some((&70, &700))).is_true();
+        BooleanAssert::assert_that(table.floor(&5).is_none()).is_true();
+        BooleanAssert::assert_that(table.ceiling(&95).is_none()).is_true();
+        NumericAssert::assert_that(actual(table.rank(&70)))
+            .is_equal()
+            .to(expected(4));
+        BooleanAssert::assert_that(table.select(4) == Some((&70, &700))).is_true();
+        ListAssert::assert_that(actual_vec(copied(table.keys_in_range(20..=70))))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![20, 30, 50, 70]))
+            .in_order();
+        NumericAssert::assert_that(actual(table.size_in_range(25..)))
+            .is_equal()
+            .to(expected(5));
+
+        table.delete(&50);
+        BooleanAssert::assert_that(table.contains(&50)).is_false();
+        BooleanAssert::assert_that(table.delete_min() == Some((10, 100))).is_true();
+        BooleanAssert::assert_that(table.delete_max() == Some((90, 900))).is_true();
+        ListAssert::assert_that(actual_vec(copied(table.keys_in_range(..))))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![20, 30, 70, 80]))
+            .in_order();
+    }
+
+    fn same_as_btree_map<T>(mut table: T)
+    where
+        T: OrderedSymbolTable<u32, u32>,
+    {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut map = BTreeMap::new();
+        for _ in 0..3000 {
+            let key: u32 = rng.gen_range(0..200);
+            match rng.gen_range(0..6) {
+                0 | 1 => {
+                    table.delete(&key);
+                    map.remove(&key);
+                }
+                2 => {
+                    BooleanAssert::assert_that(table.delete_min() == map.pop_first()).is_true();
+                }
+                3 => {
+                    BooleanAssert::assert_that(table.delete_max() == map.pop_last()).is_true();
+                }
+                _ => {
+                    let value = rng.gen();
+                    table.put(key, value);
+                    map.insert(key, value);
+                }
+            }
+
+            NumericAssert::assert_that(actual(table.len()))
+                .is_equal()
+                .to(expected(map.len()));
+            BooleanAssert::assert_that(table.get(&key) == map.get(&key)).is_true();
+            BooleanAssert::assert_that(table.floor(&key) == map.range(..=key).next_back())
+                .is_true();
+            BooleanAssert::assert_that(table.ceiling(&key) == map.range(key..).next()).is_true();
+            NumericAssert::assert_that(actual(table.rank(&key)))
+                .is_equal()
+                .to(expected(map.range(..key).count()));
+            let rank = key as usize % (map.len() + 1);
+            BooleanAssert::assert_that(table.select(rank) == map.iter().nth(rank)).is_true();
+            let in_range: Vec<&u32> = map.range(key..key + 30).map(|(key, _)| key).collect();
+            BooleanAssert::assert_that(table.keys_in_range(key..key + 30) == in_range).is_true();
+            NumericAssert::assert_that(actual(table.size_in_range(key..=key + 30)))
+                .is_equal()
+                .to(expected(map.range(key..=key + 30).count()));
+        }
+    }
+
+    #[test]
+    fn symbol_table() {
+        conformance(SymbolTable::new);
+    }
+
+    #[test]
+    fn binary_search_tree() {
+        conformance(BinarySearchTree::new);
+    }
+
+    #[test]
+    fn binary_search_tree_ranges_same_as_btree_map() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut tree = BinarySearchTree::new();
+        let mut map = BTreeMap::new();
+        for _ in 0..500 {
+            let key: u32 = rng.gen_range(0..1000);
+            tree.put(key, key);
+            map.insert(key, key);
+        }
+
+        for _ in 0..200 {
+            let start = rng.gen_range(0..1000);
+            let end = rng.gen_range(start..1100);
+            BooleanAssert::assert_that(tree.range(start..end).eq(map.range(start..end))).is_true();
+            BooleanAssert::assert_that(
+                tree.range(start..=end)
+                    .rev()
+                    .eq(map.range(start..=end).rev()),
+            )
+            .is_true();
+        }
+        BooleanAssert::assert_that(tree.iter().eq(map.iter())).is_true();
+    }
+
+    #[test]
+    fn red_black_tree() {
+        conformance(RedBlackTree::new);
+    }
+
+    #[test]
+    fn avl_tree() {
+        conformance(AvlTree::new);
+    }
+
+    #[test]
+    fn b_tree() {
+        conformance(BTree::new);
+        for order in [3, 4, 5, 8] {
+            conformance(|| BTree::with_order(order));
+        }
+    }
+}
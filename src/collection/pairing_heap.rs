@@ -0,0 +1,175 @@
+use crate::collection::priority_queue::PriorityQueue;
+use std::mem;
+
+/// Mergeable priority queue which gives out the greatest element first.
+///
+/// A single heap ordered tree with any number of children per node. Insert and meld
+/// link two roots in constant time, pop pairs up the children of the root
+/// and takes amortized logarithmic time.
+pub struct PairingHeap<T> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+}
+
+struct Node<T> {
+    elem: T,
+    children: Vec<Box<Node<T>>>,
+}
+
+impl<T> PairingHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> PairingHeap<T> {
+        PairingHeap { root: None, len: 0 }
+    }
+
+    pub fn from(elements: Vec<T>) -> PairingHeap<T> {
+        let mut heap = PairingHeap::new();
+        for elem in elements {
+            heap.insert(elem);
+        }
+
+        heap
+    }
+
+    /// Moves all elements of `other` into this heap in constant time
+    pub fn meld(&mut self, mut other: PairingHeap<T>) {
+        self.len += mem::take(&mut other.len);
+        if let Some(root) = other.root.take() {
+            self.add_tree(root);
+        }
+    }
+
+    fn add_tree(&mut self, tree: Box<Node<T>>) {
+        self.root = Some(match self.root.take() {
+            Some(root) => Self::link(root, tree),
+            None => tree,
+        });
+    }
+
+    /// Makes the tree with the lesser root a child of the other one
+    fn link(mut first: Box<Node<T>>, mut second: Box<Node<T>>) -> Box<Node<T>> {
+        if second.elem > first.elem {
+            mem::swap(&mut first, &mut second);
+        }
+        first.children.push(second);
+
+        first
+    }
+
+    /// Two pass pairing: links the trees in pairs from left to right,
+    /// then links the results into one from right to left
+    fn combine(trees: Vec<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        let mut pairs = Vec::with_capacity(trees.len().div_ceil(2));
+        let mut trees = trees.into_iter();
+        while let Some(first) = trees.next() {
+            pairs.push(match trees.next() {
+                Some(second) => Self::link(first, second),
+                None => first,
+            });
+        }
+
+        pairs.into_iter().rev().reduce(Self::link)
+    }
+}
+
+impl<T> Default for PairingHeap<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    /// Sorted inserts build a path as deep as the heap is large,
+    /// so nodes are dropped without recursion
+    fn drop(&mut self) {
+        let mut nodes: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = nodes.pop() {
+            nodes.append(&mut node.children);
+        }
+    }
+}
+
+impl<T> PriorityQueue<T> for PairingHeap<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.len += 1;
+        self.add_tree(Box::new(Node {
+            elem,
+            children: Vec::new(),
+        }));
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let Node { elem, children } = *root;
+        self.root = Self::combine(children);
+        self.len -= 1;
+
+        Some(elem)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::pairing_heap::PairingHeap;
+    use crate::collection::priority_queue::PriorityQueue;
+    use crate::collection::test_utils::{assert_same_as_binary_heap, drain};
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    #[test]
+    fn meld() {
+        let mut first = PairingHeap::from(vec![3, 2, 3, 4]);
+        let second = PairingHeap::from(vec![1, 6, 9, 0, 5]);
+
+        first.meld(second);
+
+        NumericAssert::assert_that(actual(first.len()))
+            .is_equal()
+            .to(expected(9));
+        ListAssert::assert_that(actual_vec(drain(&mut first)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 5, 4, 3, 3, 2, 1, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn drop_deep_heap() {
+        // every insert becomes the new root, the old one its only child
+        let heap = PairingHeap::from((0..1_000_000).collect());
+
+        NumericAssert::assert_that(actual(heap.len()))
+            .is_equal()
+            .to(expected(1_000_000));
+    }
+
+    #[test]
+    fn same_as_binary_heap() {
+        assert_same_as_binary_heap(PairingHeap::new, PairingHeap::meld);
+    }
+
+    #[test]
+    fn empty() {
+        let mut heap: PairingHeap<i32> = PairingHeap::new();
+
+        OptionAssert::assert_that(heap.peek().copied()).is_none();
+        OptionAssert::assert_that(heap.pop()).is_none();
+    }
+}
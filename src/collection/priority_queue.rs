@@ -0,0 +1,157 @@
+use crate::collection::simple_binary_heap::SimpleBinaryHeap;
+use std::cmp::Reverse;
+
+/// Collection which gives out its elements from the one with the highest priority
+pub trait PriorityQueue<T> {
+    fn insert(&mut self, elem: T);
+
+    /// Element with the highest priority, `None` if the queue is empty
+    fn peek(&self) -> Option<&T>;
+
+    /// Removes the element with the highest priority, `None` if the queue is empty
+    fn pop(&mut self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Priority queue which gives out the greatest element first
+pub type MaxHeap<T> = SimpleBinaryHeap<T>;
+
+impl<T> PriorityQueue<T> for SimpleBinaryHeap<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.add(elem);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SimpleBinaryHeap::peek(self)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.delete_max()
+    }
+
+    fn len(&self) -> usize {
+        SimpleBinaryHeap::len(self)
+    }
+}
+
+/// Priority queue which gives out the least element first
+pub struct MinHeap<T> {
+    heap: SimpleBinaryHeap<Reverse<T>>,
+}
+
+impl<T> MinHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> MinHeap<T> {
+        MinHeap {
+            heap: SimpleBinaryHeap::new(),
+        }
+    }
+
+    pub fn from(elements: Vec<T>) -> MinHeap<T> {
+        MinHeap {
+            heap: SimpleBinaryHeap::from(elements.into_iter().map(Reverse).collect()),
+        }
+    }
+}
+
+impl<T> Default for MinHeap<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PriorityQueue<T> for MinHeap<T>
+where
+    T: Ord,
+{
+    fn insert(&mut self, elem: T) {
+        self.heap.add(Reverse(elem));
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|elem| &elem.0)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.heap.delete_max().map(|elem| elem.0)
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::priority_queue::{MaxHeap, MinHeap, PriorityQueue};
+    use crate::collection::test_utils::drain;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    #[test]
+    fn basics_max_heap() {
+        let mut queue: MaxHeap<i32> = MaxHeap::new();
+        for value in [3, 2, 3, 4, 1, 6, 9, 0] {
+            queue.insert(value);
+        }
+
+        NumericAssert::assert_that(actual(queue.peek().copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(9));
+        NumericAssert::assert_that(actual(PriorityQueue::len(&queue)))
+            .is_equal()
+            .to(expected(8));
+        ListAssert::assert_that(actual_vec(drain(&mut queue)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 4, 3, 3, 2, 1, 0]))
+            .in_order();
+        BooleanAssert::assert_that(PriorityQueue::is_empty(&queue)).is_true();
+    }
+
+    #[test]
+    fn basics_min_heap() {
+        let mut queue = MinHeap::from(vec![3, 2, 3, 4]);
+        for value in [1, 6, 9, 0] {
+            queue.insert(value);
+        }
+
+        NumericAssert::assert_that(actual(queue.peek().copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(0));
+        NumericAssert::assert_that(actual(queue.len()))
+            .is_equal()
+            .to(expected(8));
+        ListAssert::assert_that(actual_vec(drain(&mut queue)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+        BooleanAssert::assert_that(queue.is_empty()).is_true();
+    }
+
+    #[test]
+    fn empty() {
+        let mut max_heap: MaxHeap<i32> = MaxHeap::new();
+        let mut min_heap: MinHeap<i32> = MinHeap::new();
+
+        OptionAssert::assert_that(PriorityQueue::peek(&max_heap).copied()).is_none();
+        OptionAssert::assert_that(max_heap.pop()).is_none();
+        OptionAssert::assert_that(min_heap.peek().copied()).is_none();
+        OptionAssert::assert_that(min_heap.pop()).is_none();
+    }
+}
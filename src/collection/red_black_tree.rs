@@ -0,0 +1,578 @@
+use crate::collection::search_tree;
+use crate::collection::search_tree::{InOrder, SearchNode};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::RangeBounds;
+
+/// Left-leaning red-black binary search tree: a 2-3 tree where a 3-node is a black node
+/// with a red left child. Every path from the root to a leaf has the same number
+/// of black links, so the height stays below `2 lg N` whatever the order of the keys.
+pub struct RedBlackTree<K, V>
+where
+    K: Ord,
+{
+    root: Link<K, V>,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Color {
+    Red,
+    Black,
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+    /// Color of the link from the parent
+    color: Color,
+    /// Number of nodes in the subtree rooted here
+    size: usize,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Node<K, V> {
+        Node {
+            key,
+            value,
+            left: None,
+            right: None,
+            color: Color::Red,
+            size: 1,
+        }
+    }
+
+    fn update_size(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+impl<K, V> SearchNode for Node<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn value(&self) -> &V {
+        &self.value
+    }
+
+    fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+fn is_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| node.color == Color::Red)
+}
+
+/// Whether the left child of the node behind `link` is red
+fn is_left_red<K, V>(link: &Link<K, V>) -> bool {
+    link.as_ref().is_some_and(|node| is_red(&node.left))
+}
+
+impl<K, V> Default for RedBlackTree<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> RedBlackTree<K, V>
+where
+    K: Ord,
+{
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).map(|node| &node.value)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        search_tree::find(self.root.as_deref(), key).is_some()
+    }
+
+    /// Number of keys in the tree
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        let mut root = Self::upsert(self.root.take(), key, value);
+        root.color = Color::Black;
+        self.root = Some(root);
+    }
+
+    /// The least key and its value
+    pub fn min(&self) -> Option<(&K, &V)> {
+        search_tree::min(self.root.as_deref()).map(Node::entry)
+    }
+
+    /// The greatest key and its value
+    pub fn max(&self) -> Option<(&K, &V)> {
+        search_tree::max(self.root.as_deref()).map(Node::entry)
+    }
+
+    /// The greatest key less than or equal to `key` and its value
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::floor(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// The least key greater than or equal to `key` and its value
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        search_tree::ceiling(self.root.as_deref(), key).map(Node::entry)
+    }
+
+    /// Number of keys less than `key`
+    pub fn rank(&self, key: &K) -> usize {
+        search_tree::rank(self.root.as_deref(), key)
+    }
+
+    /// The key of rank `rank` (exactly `rank` keys are less than it) and its value,
+    /// `None` if there are not that many keys
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        search_tree::select(self.root.as_deref(), rank).map(Node::entry)
+    }
+
+    /// Keys in `range` in ascending order
+    pub fn keys_in_range<R>(&self, range: R) -> Vec<&K>
+    where
+        R: RangeBounds<K>,
+    {
+        self.range(range).map(|(key, _)| key).collect()
+    }
+
+    /// Number of keys in `range`, found by ranks of its bounds without visiting the keys
+    pub fn size_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<K>,
+    {
+        search_tree::size_in_range(self.root.as_deref(), &range)
+    }
+
+    /// Keys and values in ascending order of the keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.range(..)
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    /// Keys in `range` and their values in ascending order of the keys
+    pub fn range<R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        Iter {
+            in_order: InOrder::new(self.root.as_deref(), &range),
+        }
+    }
+
+    /// Removes the least key, returns it with its value
+    pub fn delete_min(&mut self) -> Option<(K, V)> {
+        let mut root = self.root.take()?;
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.color = Color::Red;
+        }
+        let (root, deleted) = Self::_delete_min(root);
+        self.set_root(root);
+        Some(deleted)
+    }
+
+    /// Removes the greatest key, returns it with its value
+    pub fn delete_max(&mut self) -> Option<(K, V)> {
+        let mut root = self.root.take()?;
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.color = Color::Red;
+        }
+        let (root, deleted) = Self::_delete_max(root);
+        self.set_root(root);
+        Some(deleted)
+    }
+
+    pub fn delete<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.contains(key) {
+            return;
+        }
+        let Some(mut root) = self.root.take() else {
+            return;
+        };
+        if !is_red(&root.left) && !is_red(&root.right) {
+            root.color = Color::Red;
+        }
+        let (root, _) = Self::_delete(root, key);
+        self.set_root(root);
+    }
+
+    fn set_root(&mut self, root: Link<K, V>) {
+        self.root = root.map(|mut root| {
+            root.color = Color::Black;
+            root
+        });
+    }
+
+    fn upsert(link: Link<K, V>, key: K, value: V) -> Box<Node<K, V>> {
+        let Some(mut node) = link else {
+            return Box::new(Node::new(key, value));
+        };
+        match key.cmp(&node.key) {
+            Ordering::Less => node.left = Some(Self::upsert(node.left.take(), key, value)),
+            Ordering::Equal => node.value = value,
+            Ordering::Greater => node.right = Some(Self::upsert(node.right.take(), key, value)),
+        }
+        Self::balance(node)
+    }
+
+    /// The node or its left child is red on the way down, so the deleted node
+    /// is never a 2-node
+    fn _delete_min(mut node: Box<Node<K, V>>) -> (Link<K, V>, (K, V)) {
+        if node.left.is_none() {
+            // left leaning: a node without a left child has no right child either
+            return (None, (node.key, node.value));
+        }
+        if !is_red(&node.left) && !is_left_red(&node.left) {
+            node = Self::move_red_left(node);
+        }
+        let (left, deleted) = Self::_delete_min(node.left.take().expect("checked above"));
+        node.left = left;
+        (Some(Self::balance(node)), deleted)
+    }
+
+    fn _delete_max(mut node: Box<Node<K, V>>) -> (Link<K, V>, (K, V)) {
+        if is_red(&node.left) {
+            node = Self::rotate_right(node);
+        }
+        if node.right.is_none() {
+            return (None, (node.key, node.value));
+        }
+        if !is_red(&node.right) && !is_left_red(&node.right) {
+            node = Self::move_red_right(node);
+        }
+        let (right, deleted) = Self::_delete_max(node.right.take().expect("checked above"));
+        node.right = right;
+        (Some(Self::balance(node)), deleted)
+    }
+
+    /// `key` has to be in the subtree of `node`
+    fn _delete<Q>(mut node: Box<Node<K, V>>, key: &Q) -> (Link<K, V>, (K, V))
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let deleted;
+        if key < node.key.borrow() {
+            if !is_red(&node.left) && !is_left_red(&node.left) {
+                node = Self::move_red_left(node);
+            }
+            let (left, removed) = Self::_delete(node.left.take().expect("key is on the left"), key);
+            node.left = left;
+            deleted = removed;
+        } else {
+            if is_red(&node.left) {
+                node = Self::rotate_right(node);
+            }
+            if key == node.key.borrow() && node.right.is_none() {
+                return (None, (node.key, node.value));
+            }
+            if !is_red(&node.right) && !is_left_red(&node.right) {
+                node = Self::move_red_right(node);
+            }
+            let right = node.right.take().expect("key is here or on the right");
+            if key == node.key.borrow() {
+                // the successor takes the place of the deleted key
+                let (right, (min_key, min_value)) = Self::_delete_min(right);
+                node.right = right;
+                deleted = (
+                    mem::replace(&mut node.key, min_key),
+                    mem::replace(&mut node.value, min_value),
+                );
+            } else {
+                let (right, removed) = Self::_delete(right, key);
+                node.right = right;
+                deleted = removed;
+            }
+        }
+        (Some(Self::balance(node)), deleted)
+    }
+
+    fn rotate_left(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut right = node.right.take().expect("rotated link is red");
+        node.right = right.left.take();
+        right.color = node.color;
+        node.color = Color::Red;
+        right.size = node.size;
+        node.update_size();
+        right.left = Some(node);
+        right
+    }
+
+    fn rotate_right(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        let mut left = node.left.take().expect("rotated link is red");
+        node.left = left.right.take();
+        left.color = node.color;
+        node.color = Color::Red;
+        left.size = node.size;
+        node.update_size();
+        left.right = Some(node);
+        left
+    }
+
+    /// Splits a temporary 4-node on the way up or joins three 2-nodes on the way down
+    fn flip_colors(node: &mut Node<K, V>) {
+        node.color = Self::flipped(node.color);
+        for child in [&mut node.left, &mut node.right].into_iter().flatten() {
+            child.color = Self::flipped(child.color);
+        }
+    }
+
+    fn flipped(color: Color) -> Color {
+        match color {
+            Color::Red => Color::Black,
+            Color::Black => Color::Red,
+        }
+    }
+
+    /// Makes the left child or one of its children red, borrowing from the right sibling
+    fn move_red_left(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        Self::flip_colors(&mut node);
+        if is_left_red(&node.right) {
+            node.right = node.right.take().map(Self::rotate_right);
+            node = Self::rotate_left(node);
+            Self::flip_colors(&mut node);
+        }
+        node
+    }
+
+    /// Makes the right child or one of its children red, borrowing from the left sibling
+    fn move_red_right(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        Self::flip_colors(&mut node);
+        if is_left_red(&node.left) {
+            node = Self::rotate_right(node);
+            Self::flip_colors(&mut node);
+        }
+        node
+    }
+
+    /// Restores the left leaning invariants on the way up
+    fn balance(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+        if is_red(&node.right) && !is_red(&node.left) {
+            node = Self::rotate_left(node);
+        }
+        if is_red(&node.left) && is_left_red(&node.left) {
+            node = Self::rotate_right(node);
+        }
+        if is_red(&node.left) && is_red(&node.right) {
+            Self::flip_colors(&mut node);
+        }
+        node.update_size();
+        node
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    in_order: InOrder<'a, Node<K, V>>,
+}
+
+impl<'a, K, V> IntoIterator for &'a RedBlackTree<K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.in_order.next().map(Node::entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.in_order.remaining, Some(self.in_order.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.in_order.next_back().map(Node::entry)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> where K: Ord {}
+
+pub struct Keys<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> where K: Ord {}
+
+pub struct Values<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V>
+where
+    K: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> where K: Ord {}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::red_black_tree::{is_red, Color, Link, RedBlackTree};
+    use crate::collection::test_utils::assert_stays_balanced;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, expected};
+
+    /// Number of black links from the node to any of its leaves,
+    /// `None` if two paths have different counts
+    fn black_height<K, V>(link: &Link<K, V>) -> Option<usize> {
+        let Some(node) = link else {
+            return Some(0);
+        };
+        let left = black_height(&node.left)?;
+        let right = black_height(&node.right)?;
+        if left != right {
+            return None;
+        }
+        Some(left + usize::from(node.color == Color::Black))
+    }
+
+    /// Red links lean left, no node has two red links and subtree sizes add up
+    fn is_left_leaning<K, V>(link: &Link<K, V>) -> bool {
+        let Some(node) = link else {
+            return true;
+        };
+        let two_reds_in_a_row = node.color == Color::Red && is_red(&node.left);
+        !is_red(&node.right)
+            && !two_reds_in_a_row
+            && node.size == 1 + super::size(&node.left) + super::size(&node.right)
+            && is_left_leaning(&node.left)
+            && is_left_leaning(&node.right)
+    }
+
+    fn height<K, V>(link: &Link<K, V>) -> usize {
+        link.as_ref()
+            .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+    }
+
+    fn assert_balanced<K, V>(tree: &RedBlackTree<K, V>)
+    where
+        K: Ord,
+    {
+        BooleanAssert::assert_that(black_height(&tree.root).is_some()).is_true();
+        BooleanAssert::assert_that(is_left_leaning(&tree.root)).is_true();
+        BooleanAssert::assert_that(!is_red(&tree.root)).is_true();
+        // every path has the same black links and at most as many red ones
+        let levels = (usize::BITS - tree.len().leading_zeros()) as usize;
+        NumericAssert::assert_that(actual(height(&tree.root)))
+            .is_less_or_equal()
+            .to(expected(2 * levels));
+    }
+
+    #[test]
+    fn stays_balanced() {
+        assert_stays_balanced(RedBlackTree::new, assert_balanced);
+    }
+}
@@ -0,0 +1,254 @@
+//! Read-only operations shared by the binary search trees: they only differ
+//! in how they restructure nodes, every query walks the nodes the same way.
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+/// Node of a binary search tree which keeps the number of nodes in its subtree
+pub(crate) trait SearchNode: Sized {
+    type Key: Ord;
+    type Value;
+
+    fn key(&self) -> &Self::Key;
+
+    fn value(&self) -> &Self::Value;
+
+    fn left(&self) -> Option<&Self>;
+
+    fn right(&self) -> Option<&Self>;
+
+    fn size(&self) -> usize;
+
+    fn entry(&self) -> (&Self::Key, &Self::Value) {
+        (self.key(), self.value())
+    }
+}
+
+pub(crate) fn size<N>(node: Option<&N>) -> usize
+where
+    N: SearchNode,
+{
+    node.map_or(0, N::size)
+}
+
+pub(crate) fn find<'a, N, Q>(root: Option<&'a N>, key: &Q) -> Option<&'a N>
+where
+    N: SearchNode,
+    N::Key: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut current = root;
+    while let Some(node) = current {
+        match key.cmp(node.key().borrow()) {
+            Ordering::Less => current = node.left(),
+            Ordering::Equal => return Some(node),
+            Ordering::Greater => current = node.right(),
+        }
+    }
+    None
+}
+
+pub(crate) fn min<N>(root: Option<&N>) -> Option<&N>
+where
+    N: SearchNode,
+{
+    let mut current = root?;
+    while let Some(left) = current.left() {
+        current = left;
+    }
+    Some(current)
+}
+
+pub(crate) fn max<N>(root: Option<&N>) -> Option<&N>
+where
+    N: SearchNode,
+{
+    let mut current = root?;
+    while let Some(right) = current.right() {
+        current = right;
+    }
+    Some(current)
+}
+
+/// Node with the greatest key less than or equal to `key`
+pub(crate) fn floor<'a, N>(root: Option<&'a N>, key: &N::Key) -> Option<&'a N>
+where
+    N: SearchNode,
+{
+    let mut current = root;
+    let mut floor = None;
+    while let Some(node) = current {
+        match key.cmp(node.key()) {
+            Ordering::Less => current = node.left(),
+            Ordering::Equal => return Some(node),
+            Ordering::Greater => {
+                floor = Some(node);
+                current = node.right();
+            }
+        }
+    }
+    floor
+}
+
+/// Node with the least key greater than or equal to `key`
+pub(crate) fn ceiling<'a, N>(root: Option<&'a N>, key: &N::Key) -> Option<&'a N>
+where
+    N: SearchNode,
+{
+    let mut current = root;
+    let mut ceiling = None;
+    while let Some(node) = current {
+        match key.cmp(node.key()) {
+            Ordering::Less => {
+                ceiling = Some(node);
+                current = node.left();
+            }
+            Ordering::Equal => return Some(node),
+            Ordering::Greater => current = node.right(),
+        }
+    }
+    ceiling
+}
+
+/// Number of keys less than `key`
+pub(crate) fn rank<N>(root: Option<&N>, key: &N::Key) -> usize
+where
+    N: SearchNode,
+{
+    let mut current = root;
+    let mut rank = 0;
+    while let Some(node) = current {
+        match key.cmp(node.key()) {
+            Ordering::Less => current = node.left(),
+            Ordering::Equal => return rank + size(node.left()),
+            Ordering::Greater => {
+                rank += 1 + size(node.left());
+                current = node.right();
+            }
+        }
+    }
+    rank
+}
+
+/// Node with exactly `rank` keys less than its key
+pub(crate) fn select<N>(root: Option<&N>, mut rank: usize) -> Option<&N>
+where
+    N: SearchNode,
+{
+    let mut current = root;
+    while let Some(node) = current {
+        let left_size = size(node.left());
+        match rank.cmp(&left_size) {
+            Ordering::Less => current = node.left(),
+            Ordering::Equal => return Some(node),
+            Ordering::Greater => {
+                rank -= left_size + 1;
+                current = node.right();
+            }
+        }
+    }
+    None
+}
+
+/// Number of keys in `range`, found by ranks of its bounds without visiting the keys
+pub(crate) fn size_in_range<N, R>(root: Option<&N>, range: &R) -> usize
+where
+    N: SearchNode,
+    R: RangeBounds<N::Key>,
+{
+    let contains = |key| usize::from(find(root, key).is_some());
+    let below_start = match range.start_bound() {
+        Bound::Included(start) => rank(root, start),
+        Bound::Excluded(start) => rank(root, start) + contains(start),
+        Bound::Unbounded => 0,
+    };
+    let below_end = match range.end_bound() {
+        Bound::Included(end) => rank(root, end) + contains(end),
+        Bound::Excluded(end) => rank(root, end),
+        Bound::Unbounded => size(root),
+    };
+    below_end.saturating_sub(below_start)
+}
+
+/// In order walk over a range of keys from both ends
+pub(crate) struct InOrder<'a, N> {
+    /// Next nodes from the front and their ancestors which come after them
+    front: Vec<&'a N>,
+    /// Next nodes from the back and their ancestors which come before them
+    back: Vec<&'a N>,
+    /// Keeps the two ends from passing each other
+    pub(crate) remaining: usize,
+}
+
+impl<'a, N> InOrder<'a, N>
+where
+    N: SearchNode,
+{
+    pub(crate) fn new<R>(root: Option<&'a N>, range: &R) -> InOrder<'a, N>
+    where
+        R: RangeBounds<N::Key>,
+    {
+        let mut in_order = InOrder {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: size_in_range(root, range),
+        };
+        let mut current = root;
+        while let Some(node) = current {
+            let after_start = match range.start_bound() {
+                Bound::Included(start) => node.key() >= start,
+                Bound::Excluded(start) => node.key() > start,
+                Bound::Unbounded => true,
+            };
+            if after_start {
+                in_order.front.push(node);
+                current = node.left();
+            } else {
+                current = node.right();
+            }
+        }
+        let mut current = root;
+        while let Some(node) = current {
+            let before_end = match range.end_bound() {
+                Bound::Included(end) => node.key() <= end,
+                Bound::Excluded(end) => node.key() < end,
+                Bound::Unbounded => true,
+            };
+            if before_end {
+                in_order.back.push(node);
+                current = node.right();
+            } else {
+                current = node.left();
+            }
+        }
+        in_order
+    }
+
+    pub(crate) fn next(&mut self) -> Option<&'a N> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = self.front.pop()?;
+        let mut current = node.right();
+        while let Some(child) = current {
+            self.front.push(child);
+            current = child.left();
+        }
+        Some(node)
+    }
+
+    pub(crate) fn next_back(&mut self) -> Option<&'a N> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = self.back.pop()?;
+        let mut current = node.left();
+        while let Some(child) = current {
+            self.back.push(child);
+            current = child.right();
+        }
+        Some(node)
+    }
+}
@@ -1,15 +1,14 @@
-use std::fmt::Debug;
+use crate::collection::d_ary_heap;
+use crate::operations::SortOperations;
+use std::cmp::Ordering;
 
-pub struct SimpleBinaryHeap<T>
-where
-    T: Ord + Debug,
-{
+pub struct SimpleBinaryHeap<T> {
     elements: Vec<T>,
 }
 
 impl<T> SimpleBinaryHeap<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
     pub fn new() -> SimpleBinaryHeap<T> {
         SimpleBinaryHeap {
@@ -19,110 +18,162 @@ where
 
     pub fn from(elements: Vec<T>) -> SimpleBinaryHeap<T> {
         let mut heap = SimpleBinaryHeap { elements };
-        let mut i = heap.elements.len() / 2;
-        loop {
-            heap.sink_till(i, None);
-            if i == 0 {
-                break;
-            }
-            i -= 1;
-        }
+        Self::heapify(&mut heap.elements, &mut T::cmp);
 
         heap
     }
 
     pub fn add(&mut self, elem: T) {
         self.elements.push(elem);
-        self.pop_up(self.elements.len() - 1);
+        let last_index = self.elements.len() - 1;
+        Self::pop_up(&mut self.elements, last_index, &mut T::cmp);
     }
 
-    pub fn delete_max(&mut self) -> T {
+    /// Removes the greatest element, `None` if the heap is empty
+    pub fn delete_max(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
         let removed = self.elements.swap_remove(0);
-        self.sink_till(0, None);
+        let len = self.elements.len();
+        Self::sink_till(&mut self.elements, 0, len, &mut T::cmp);
+
+        Some(removed)
+    }
+
+    /// Removes the least element, `None` if the heap is empty.
+    ///
+    /// The least element is one of the leaves, so it takes time proportional to N,
+    /// [`crate::collection::priority_queue::MinHeap`] or
+    /// [`crate::collection::min_max_heap::MinMaxHeap`] take logarithmic time.
+    pub fn delete_min(&mut self) -> Option<T> {
+        let len = self.elements.len();
+        let min_index = (len / 2..len)
+            .min_by(|first, second| self.elements[*first].cmp(&self.elements[*second]))?;
+        let removed = self.elements.swap_remove(min_index);
+        // the last leaf took the place of the removed one, it can only be too big for it
+        if min_index < self.elements.len() {
+            Self::pop_up(&mut self.elements, min_index, &mut T::cmp);
+        }
+
+        Some(removed)
+    }
+
+    /// The greatest element, `None` if the heap is empty
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.first()
+    }
 
-        removed
+    pub fn len(&self) -> usize {
+        self.elements.len()
     }
 
-    pub fn delete_min(&mut self) -> T {
-        self.elements.remove(self.elements.len() - 1)
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
     }
 
     pub fn sort(mut self) -> Vec<T> {
-        let mut count = self.elements.len() - 1;
+        Self::sort_slice(&mut self.elements);
+
+        self.elements
+    }
+
+    /// Heap sort in place: the slice itself is used as the heap storage
+    pub fn sort_slice(elements: &mut [T]) {
+        Self::sort_slice_by(elements, T::cmp);
+    }
+}
+
+impl<T> SimpleBinaryHeap<T> {
+    /// Same as [`SimpleBinaryHeap::sort_slice`], but the heap is ordered by `compare`,
+    /// so the greatest element according to it ends up last
+    pub fn sort_slice_by<F>(elements: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        d_ary_heap::sort_slice_with_operations(elements, 2, &mut compare);
+    }
+
+    fn heapify<O>(elements: &mut [T], operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        let len = elements.len();
+        let mut i = len / 2;
         loop {
-            if count == 0 {
+            Self::sink_till(elements, i, len, operations);
+            if i == 0 {
                 break;
             }
-            self.elements.swap(0, count);
-            self.sink_till(0, Some(count - 1));
-            count -= 1;
+            i -= 1;
         }
-
-        self.elements
     }
 
-    fn pop_up(&mut self, index: usize) {
+    pub(crate) fn pop_up<O>(elements: &mut [T], index: usize, operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
         if index == 0 {
             return;
         }
         let parent_index = (index - 1) / 2;
-        let current = &self.elements[index];
-        let parent = &self.elements[parent_index];
-        if current.gt(parent) {
-            self.elements.swap(index, parent_index);
-            self.pop_up(parent_index);
+        let current = &elements[index];
+        let parent = &elements[parent_index];
+        if operations.compare(current, parent).is_gt() {
+            operations.swap(elements, index, parent_index);
+            Self::pop_up(elements, parent_index, operations);
         }
     }
 
-    fn sink_till(&mut self, index: usize, till: Option<usize>) {
-        if index >= self.elements.len() {
+    /// Sinks the element at `index`, only elements before `till` (exclusive) belong to the heap
+    pub(crate) fn sink_till<O>(elements: &mut [T], index: usize, till: usize, operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        if index >= till {
             return;
         }
 
         let left_child_index = SimpleBinaryHeap::<T>::left_child_index_for(index);
         let right_child_index = SimpleBinaryHeap::<T>::right_child_index_for(index);
 
-        let mut left_child = self.elements.get(left_child_index);
-        let mut right_child = self.elements.get(right_child_index);
-        if let Some(till_index) = till {
-            if left_child_index >= till_index {
-                left_child = None
-            }
-            if right_child_index >= till_index {
-                right_child = None
-            }
-        }
+        let heap = &elements[..till];
+        let left_child = heap.get(left_child_index);
+        let right_child = heap.get(right_child_index);
 
-        let current = &self.elements[index];
+        let current = &heap[index];
         match (left_child, right_child) {
             (Some(left_child), Some(right_child)) => {
-                if left_child.gt(right_child) {
-                    self.swap_if_child_gt(index, left_child_index, till);
-                } else if right_child.gt(current) {
-                    self.swap_if_child_gt(index, right_child_index, till);
+                if operations.compare(left_child, right_child).is_gt() {
+                    Self::swap_if_child_gt(elements, index, left_child_index, till, operations);
+                } else if operations.compare(right_child, current).is_gt() {
+                    Self::swap_if_child_gt(elements, index, right_child_index, till, operations);
                 }
             }
             (Some(_), None) => {
-                self.swap_if_child_gt(index, left_child_index, till);
+                Self::swap_if_child_gt(elements, index, left_child_index, till, operations);
             }
             (None, Some(_)) => {
-                self.swap_if_child_gt(index, right_child_index, till);
+                Self::swap_if_child_gt(elements, index, right_child_index, till, operations);
             }
             (None, None) => {}
         }
     }
 
-    fn swap_if_child_gt(
-        &mut self,
+    fn swap_if_child_gt<O>(
+        elements: &mut [T],
         current_index: usize,
         child_index: usize,
-        sink_till: Option<usize>,
-    ) {
-        let current = &self.elements[current_index];
-        let child = &self.elements[child_index];
-        if child.gt(current) {
-            self.elements.swap(current_index, child_index);
-            self.sink_till(child_index, sink_till);
+        sink_till: usize,
+        operations: &mut O,
+    ) where
+        O: SortOperations<T>,
+    {
+        let current = &elements[current_index];
+        let child = &elements[child_index];
+        if operations.compare(child, current).is_gt() {
+            operations.swap(elements, current_index, child_index);
+            Self::sink_till(elements, child_index, sink_till, operations);
         }
     }
 
@@ -137,7 +188,7 @@ where
 
 impl<T> Default for SimpleBinaryHeap<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
     fn default() -> Self {
         Self::new()
@@ -148,6 +199,7 @@ where
 mod test {
     use crate::collection::simple_binary_heap::SimpleBinaryHeap;
     use easy_assert::list_assertions::ListAssert;
+    use easy_assert::option_assertions::OptionAssert;
     use easy_assert::string_assertions::StringAssert;
     use easy_assert::{actual, actual_vec, expected, expected_vec};
 
@@ -176,15 +228,15 @@ mod test {
             binary_heap.add(value);
         }
 
-        let removed = binary_heap.delete_min();
+        let removed = binary_heap.delete_min().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
-            .to(expected("H".to_string()));
+            .to(expected("A".to_string()));
 
         ListAssert::assert_that(actual_vec(binary_heap.elements))
             .with_element_matcher(|a, b| a.eq(b))
             .is_equal_to(expected_vec(vec![
-                "T", "S", "R", "N", "P", "O", "A", "E", "I", "G",
+                "T", "S", "R", "N", "P", "O", "H", "E", "I", "G",
             ]))
             .in_order();
     }
@@ -198,7 +250,7 @@ mod test {
             binary_heap.add(value);
         }
 
-        let removed = binary_heap.delete_max();
+        let removed = binary_heap.delete_max().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
             .to(expected("T".to_string()));
@@ -220,11 +272,11 @@ mod test {
             binary_heap.add(value);
         }
 
-        let removed = binary_heap.delete_max();
+        let removed = binary_heap.delete_max().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
             .to(expected("T".to_string()));
-        let removed = binary_heap.delete_max();
+        let removed = binary_heap.delete_max().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
             .to(expected("S".to_string()));
@@ -246,11 +298,11 @@ mod test {
             binary_heap.add(value);
         }
 
-        let removed = binary_heap.delete_max();
+        let removed = binary_heap.delete_max().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
             .to(expected("T".to_string()));
-        let removed = binary_heap.delete_max();
+        let removed = binary_heap.delete_max().unwrap_or_default();
         StringAssert::assert_that(actual(removed.to_string()))
             .is_equal()
             .to(expected("S".to_string()));
@@ -278,6 +330,18 @@ mod test {
             .in_order();
     }
 
+    #[test]
+    fn sort_with_duplicates() {
+        let array = vec![5, 5, 1];
+
+        let sorted = SimpleBinaryHeap::from(array).sort();
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![1, 5, 5]))
+            .in_order();
+    }
+
     #[test]
     fn basics_sort() {
         let array = vec!["S", "O", "R", "T", "E", "X", "A", "M", "P", "L", "E"];
@@ -291,4 +355,45 @@ mod test {
             ]))
             .in_order();
     }
+
+    #[test]
+    fn basics_sort_slice_by() {
+        let mut array = vec!["S", "O", "R", "T", "E", "X", "A", "M", "P", "L", "E"];
+
+        SimpleBinaryHeap::sort_slice_by(&mut array, |a, b| b.cmp(a));
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "X", "T", "S", "R", "P", "O", "M", "L", "E", "E", "A",
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn delete_min_n() {
+        let array = vec!["T", "P", "R", "N", "H", "O", "A", "E", "I", "G", "S"];
+
+        let mut binary_heap: SimpleBinaryHeap<&str> = SimpleBinaryHeap::from(array);
+        let mut removed = Vec::new();
+        while let Some(min) = binary_heap.delete_min() {
+            removed.push(min);
+        }
+
+        ListAssert::assert_that(actual_vec(removed))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "A", "E", "G", "H", "I", "N", "O", "P", "R", "S", "T",
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn delete_from_empty() {
+        let mut binary_heap: SimpleBinaryHeap<i32> = SimpleBinaryHeap::new();
+
+        OptionAssert::assert_that(binary_heap.delete_max()).is_none();
+        OptionAssert::assert_that(binary_heap.delete_min()).is_none();
+        OptionAssert::assert_that(binary_heap.peek().copied()).is_none();
+    }
 }
@@ -0,0 +1,116 @@
+use crate::collection::priority_queue::{MaxHeap, MinHeap, PriorityQueue};
+
+/// The `k` greatest elements of `elements`, greatest first.
+///
+/// Only `k` elements are kept at a time: the least of them sits on top of a min heap
+/// and is replaced whenever a greater one comes, so it takes time proportional to N log k.
+pub fn top_k<T, I>(elements: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    let mut kept = MinHeap::new();
+    keep_k(&mut kept, elements, k, |elem, least| elem > least);
+
+    drain_reversed(kept)
+}
+
+/// The `k` least elements of `elements`, least first.
+///
+/// Same as [`top_k`] with the greatest of the kept elements on top of a max heap.
+pub fn bottom_k<T, I>(elements: I, k: usize) -> Vec<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+{
+    let mut kept = MaxHeap::new();
+    keep_k(&mut kept, elements, k, |elem, greatest| elem < greatest);
+
+    drain_reversed(kept)
+}
+
+/// Fills `kept` with `k` elements, then swaps its top for every element that `beats` it
+fn keep_k<T, Q, I, F>(kept: &mut Q, elements: I, k: usize, beats: F)
+where
+    Q: PriorityQueue<T>,
+    I: IntoIterator<Item = T>,
+    F: Fn(&T, &T) -> bool,
+{
+    if k == 0 {
+        return;
+    }
+    for elem in elements {
+        if kept.len() < k {
+            kept.insert(elem);
+        } else if kept.peek().is_some_and(|top| beats(&elem, top)) {
+            kept.pop();
+            kept.insert(elem);
+        }
+    }
+}
+
+fn drain_reversed<T, Q>(mut queue: Q) -> Vec<T>
+where
+    Q: PriorityQueue<T>,
+{
+    let mut elements = Vec::with_capacity(queue.len());
+    while let Some(elem) = queue.pop() {
+        elements.push(elem);
+    }
+    elements.reverse();
+
+    elements
+}
+
+#[cfg(test)]
+mod test {
+    use crate::collection::top_k::{bottom_k, top_k};
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn basics() {
+        let elements = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        ListAssert::assert_that(actual_vec(top_k(elements.clone(), 3)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9, 6, 4]))
+            .in_order();
+        ListAssert::assert_that(actual_vec(bottom_k(elements, 3)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_sorting() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let elements: Vec<u32> = (0..10_000).map(|_| rng.gen_range(0..1000)).collect();
+        let mut sorted = elements.clone();
+        sorted.sort();
+
+        for k in [1, 10, 100, 1000] {
+            let greatest: Vec<u32> = sorted.iter().rev().take(k).copied().collect();
+            ListAssert::assert_that(actual_vec(top_k(elements.iter().copied(), k)))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(greatest))
+                .in_order();
+            ListAssert::assert_that(actual_vec(bottom_k(elements.iter().copied(), k)))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(sorted[..k].to_vec()))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn k_out_of_bounds() {
+        ListAssert::assert_that(actual_vec(top_k(vec![2, 1, 3], 5)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![3, 2, 1]))
+            .in_order();
+        BooleanAssert::assert_that(bottom_k(vec![2, 1, 3], 0).is_empty()).is_true();
+    }
+}
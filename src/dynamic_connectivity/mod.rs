@@ -17,6 +17,6 @@ mod test_utils {
     }
 
     fn to_any(obj: &(impl UnionFind + 'static)) -> &dyn Any {
-        return obj;
+        obj
     }
 }
@@ -263,7 +263,7 @@ mod tests {
         let result = quick_union.connected(&0, &1);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_false()
+        BooleanAssert::assert_that(result.expect("value")).is_false()
     }
 
     #[test]
@@ -276,7 +276,7 @@ mod tests {
         let result = quick_union.connected(&1, &3);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_true()
+        BooleanAssert::assert_that(result.expect("value")).is_true()
     }
 
     #[test]
@@ -289,7 +289,7 @@ mod tests {
         let result = quick_union.connected(&1, &7);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_false()
+        BooleanAssert::assert_that(result.expect("value")).is_false()
     }
 
     #[test]
@@ -302,7 +302,7 @@ mod tests {
         let result = quick_union.connected(&1, &7);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_true()
+        BooleanAssert::assert_that(result.expect("value")).is_true()
     }
 
     #[test]
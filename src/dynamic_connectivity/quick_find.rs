@@ -201,7 +201,7 @@ mod tests {
         let result = quick_find.connected(&0, &1);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_false()
+        BooleanAssert::assert_that(result.expect("value")).is_false()
     }
 
     #[test]
@@ -213,6 +213,6 @@ mod tests {
         let result = quick_find.connected(&1, &3);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_true()
+        BooleanAssert::assert_that(result.expect("value")).is_true()
     }
 }
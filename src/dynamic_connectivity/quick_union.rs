@@ -214,7 +214,7 @@ mod tests {
         let result = quick_union.connected(&0, &1);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_false()
+        BooleanAssert::assert_that(result.expect("value")).is_false()
     }
 
     #[test]
@@ -226,7 +226,7 @@ mod tests {
         let result = quick_union.connected(&1, &3);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_true()
+        BooleanAssert::assert_that(result.expect("value")).is_true()
     }
 
     #[test]
@@ -238,7 +238,7 @@ mod tests {
         let result = quick_union.connected(&1, &7);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_false()
+        BooleanAssert::assert_that(result.expect("value")).is_false()
     }
 
     #[test]
@@ -250,7 +250,7 @@ mod tests {
         let result = quick_union.connected(&1, &7);
 
         BooleanAssert::assert_that(result.is_ok()).is_true();
-        BooleanAssert::assert_that(result.ok().expect("value")).is_true()
+        BooleanAssert::assert_that(result.expect("value")).is_true()
     }
 
     #[test]
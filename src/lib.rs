@@ -2,10 +2,11 @@ extern crate core;
 
 use crate::AlgoError::MissingElement;
 use std::fmt::Display;
-use AlgoError::ElementAlreadyExist;
+use AlgoError::{ElementAlreadyExist, InvalidArgument};
 
 pub mod collection;
 pub mod dynamic_connectivity;
+pub mod operations;
 pub mod sort;
 
 pub fn add(left: usize, right: usize) -> usize {
@@ -16,6 +17,7 @@ pub fn add(left: usize, right: usize) -> usize {
 pub enum AlgoError {
     MissingElement(String),
     ElementAlreadyExist(String),
+    InvalidArgument(String),
 }
 
 impl AlgoError {
@@ -45,10 +47,22 @@ impl AlgoError {
         ))
     }
 
+    fn invalid_argument(
+        element_name: &str,
+        element_value: &dyn Display,
+        reason: &str,
+    ) -> AlgoError {
+        InvalidArgument(format!(
+            "{} with value {} {}",
+            element_name, element_value, reason
+        ))
+    }
+
     pub fn to_readable_string(&self) -> String {
         match self {
             MissingElement(value) => value.clone(),
             ElementAlreadyExist(value) => value.clone(),
+            InvalidArgument(value) => value.clone(),
         }
     }
 }
@@ -0,0 +1,25 @@
+use std::cmp::Ordering;
+
+/// Everything a comparison sort or a heap does with the elements: comparing and moving them.
+///
+/// Every comparator closure implements it, [`crate::sort::instrumentation::Instrumented`]
+/// also counts the operations, the index priority queues track where every index moves.
+pub trait SortOperations<T> {
+    fn compare(&mut self, first: &T, second: &T) -> Ordering;
+
+    fn swap(&mut self, array: &mut [T], first: usize, second: usize) {
+        array.swap(first, second);
+    }
+
+    /// Called when `count` elements are moved without swapping, e.g. copied while merging
+    fn moved(&mut self, _count: usize) {}
+}
+
+impl<T, F> SortOperations<T> for F
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn compare(&mut self, first: &T, second: &T) -> Ordering {
+        self(first, second)
+    }
+}
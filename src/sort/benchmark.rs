@@ -0,0 +1,397 @@
+//! Doubling experiments: every sort runs on inputs of size N, 2N, 4N, ... and the ratio of
+//! consecutive running times estimates the exponent b of the running time a N^b.
+//!
+//! Inputs are generated from a fixed seed and randomized sorts are seeded as well, so two runs
+//! with the same [`DoublingExperiment`] sort exactly the same arrays the same way.
+use crate::sort::order::is_sorted;
+use crate::sort::parallel::ParallelConfig;
+use crate::sort::{
+    bottom_up_merge_sort, counting_sort, heap_sort, insertion_sort, intro_sort, lsd_radix_sort,
+    merge_sort, merge_sort_clone, msd_radix_sort, natural_merge_sort, quick_sort, selection_sort,
+    shell_sort, three_way_quick_sort, three_way_string_quick_sort,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// Quadratic sorts are not run on inputs longer than this
+const QUADRATIC_MAX_LEN: usize = 1 << 14;
+/// Distinct values of [`Distribution::FewUnique`]
+const FEW_UNIQUE_VALUES: u32 = 8;
+/// Every this many elements one is swapped with a random one in [`Distribution::NearlySorted`]
+const NEARLY_SORTED_SWAP_EVERY: usize = 100;
+/// Seed of the randomized sorts
+const SORT_SEED: u64 = 42;
+
+/// Shape of the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniform values from 0 to N
+    Random,
+    Sorted,
+    Reversed,
+    /// A handful of distinct values
+    FewUnique,
+    /// Ascending up to the middle and descending after it
+    OrganPipe,
+    /// Sorted with about 1% of the elements swapped with random ones
+    NearlySorted,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 6] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::Reversed,
+        Distribution::FewUnique,
+        Distribution::OrganPipe,
+        Distribution::NearlySorted,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::Reversed => "reversed",
+            Distribution::FewUnique => "few-unique",
+            Distribution::OrganPipe => "organ-pipe",
+            Distribution::NearlySorted => "nearly-sorted",
+        }
+    }
+
+    /// Input of `len` elements, all of them less than or equal to `len`
+    pub fn generate<R>(&self, len: usize, rng: &mut R) -> Vec<u32>
+    where
+        R: Rng + ?Sized,
+    {
+        let len_u32 = len as u32;
+        match self {
+            Distribution::Random => (0..len).map(|_| rng.gen_range(0..=len_u32)).collect(),
+            Distribution::Sorted => (0..len_u32).collect(),
+            Distribution::Reversed => (0..len_u32).rev().collect(),
+            Distribution::FewUnique => (0..len)
+                .map(|_| rng.gen_range(0..FEW_UNIQUE_VALUES.min(len_u32 + 1)))
+                .collect(),
+            Distribution::OrganPipe => (0..len_u32)
+                .map(|index| index.min(len_u32 - 1 - index))
+                .collect(),
+            Distribution::NearlySorted => {
+                let mut array: Vec<u32> = (0..len_u32).collect();
+                for _ in 0..len / NEARLY_SORTED_SWAP_EVERY {
+                    let first = rng.gen_range(0..len);
+                    let second = rng.gen_range(0..len);
+                    array.swap(first, second);
+                }
+                array
+            }
+        }
+    }
+}
+
+/// A sort to measure
+pub struct Algorithm {
+    pub name: &'static str,
+    pub sort: fn(&mut [u32]),
+    /// Longest input the sort is run on
+    pub max_len: usize,
+}
+
+impl Algorithm {
+    pub fn new(name: &'static str, sort: fn(&mut [u32])) -> Algorithm {
+        Algorithm {
+            name,
+            sort,
+            max_len: usize::MAX,
+        }
+    }
+
+    fn quadratic(name: &'static str, sort: fn(&mut [u32])) -> Algorithm {
+        Algorithm {
+            name,
+            sort,
+            max_len: QUADRATIC_MAX_LEN,
+        }
+    }
+}
+
+/// Every in-memory sort of [`crate::sort`]. String sorts get the big endian bytes of the values,
+/// counting sort gets the values as keys. `external_sort` is left out, it measures the disk.
+pub fn algorithms() -> Vec<Algorithm> {
+    vec![
+        Algorithm::quadratic("insertion_sort", insertion_sort::sort_slice),
+        Algorithm::quadratic("selection_sort", selection_sort::sort_slice),
+        Algorithm::new("shell_sort", shell_sort::sort_slice),
+        Algorithm::new("merge_sort", merge_sort::sort_slice),
+        Algorithm::new("merge_sort par", |array| {
+            merge_sort::par_sort_slice(array, &ParallelConfig::default())
+        }),
+        Algorithm::new("merge_sort_clone", |array| {
+            let sorted = merge_sort_clone::sort(array.to_vec());
+            array.copy_from_slice(&sorted);
+        }),
+        Algorithm::new("bottom_up_merge_sort", bottom_up_merge_sort::sort_slice),
+        Algorithm::new("natural_merge_sort", natural_merge_sort::sort_slice),
+        Algorithm::new("quick_sort", |array| {
+            quick_sort::sort_seeded(array, SORT_SEED)
+        }),
+        Algorithm::new("three_way_quick_sort", |array| {
+            three_way_quick_sort::sort_seeded(array, SORT_SEED)
+        }),
+        Algorithm::new("three_way_quick_sort par", |array| {
+            three_way_quick_sort::par_sort_by_with(
+                array,
+                u32::cmp,
+                &mut StdRng::seed_from_u64(SORT_SEED),
+                &ParallelConfig::default(),
+            )
+        }),
+        Algorithm::new("intro_sort", intro_sort::sort_slice),
+        Algorithm::new("heap_sort d=2", |array| heap_sort::sort_slice(array, 2)),
+        Algorithm::new("heap_sort d=4", |array| heap_sort::sort_slice(array, 4)),
+        Algorithm::new("heap_sort d=8", |array| heap_sort::sort_slice(array, 8)),
+        Algorithm::new("counting_sort", |array| {
+            let key_range = array.iter().max().map_or(0, |max| *max as usize + 1);
+            counting_sort::sort_by_key(array, key_range, |value| *value as usize)
+        }),
+        Algorithm::new("lsd_radix_sort", lsd_radix_sort::sort_slice),
+        Algorithm::new("msd_radix_sort", |array| {
+            sort_as_bytes(array, msd_radix_sort::sort_slice)
+        }),
+        Algorithm::new("three_way_string_quick_sort", |array| {
+            sort_as_bytes(array, three_way_string_quick_sort::sort_slice)
+        }),
+    ]
+}
+
+fn sort_as_bytes(array: &mut [u32], sort: fn(&mut [[u8; 4]])) {
+    let mut bytes: Vec<[u8; 4]> = array.iter().map(|value| value.to_be_bytes()).collect();
+    sort(&mut bytes);
+    for (value, bytes) in array.iter_mut().zip(bytes) {
+        *value = u32::from_be_bytes(bytes);
+    }
+}
+
+/// Input sizes and the seed of an experiment
+pub struct DoublingExperiment {
+    pub(crate) start_len: usize,
+    pub(crate) doublings: usize,
+    pub(crate) trials: usize,
+    pub(crate) seed: u64,
+}
+
+impl DoublingExperiment {
+    /// Sizes `start_len`, `2 * start_len`, ... `2^doublings * start_len`, each one timed as
+    /// the total of `trials` sorts of different inputs
+    pub fn new(start_len: usize, doublings: usize, trials: usize, seed: u64) -> DoublingExperiment {
+        DoublingExperiment {
+            start_len: start_len.max(1),
+            doublings,
+            trials: trials.max(1),
+            seed,
+        }
+    }
+
+    /// Times `algorithm` on growing inputs of `distribution`, sizes over its `max_len` are skipped
+    ///
+    /// # Panics
+    ///
+    /// If the algorithm does not sort an input
+    pub fn run(&self, algorithm: &Algorithm, distribution: Distribution) -> Vec<Measurement> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut measurements: Vec<Measurement> = Vec::new();
+        let mut len = self.start_len;
+        for _ in 0..=self.doublings {
+            if len > algorithm.max_len {
+                break;
+            }
+            let mut time = Duration::ZERO;
+            for _ in 0..self.trials {
+                let mut array = distribution.generate(len, &mut rng);
+                let start = Instant::now();
+                (algorithm.sort)(&mut array);
+                time += start.elapsed();
+                assert!(
+                    is_sorted(&array),
+                    "{} did not sort {} input of {} elements",
+                    algorithm.name,
+                    distribution.name(),
+                    len
+                );
+            }
+            let previous = measurements.last().map(|measurement| measurement.time);
+            measurements.push(Measurement {
+                len,
+                time,
+                ratio: previous.map(|previous| ratio(time, previous)),
+            });
+            len *= 2;
+        }
+
+        measurements
+    }
+
+    /// Runs every algorithm on every distribution and prints a table with the time of
+    /// every size and the estimated exponent of the last doubling
+    pub fn run_all<W>(&self, algorithms: &[Algorithm], output: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(
+            output,
+            "{:<30} {:<14} {:>10} {:>12} {:>8} {:>9}",
+            "algorithm", "input", "N", "time (ms)", "ratio", "exponent"
+        )?;
+        for algorithm in algorithms {
+            for distribution in Distribution::ALL {
+                for measurement in self.run(algorithm, distribution) {
+                    let (ratio, exponent) = match measurement.ratio {
+                        Some(ratio) => (format!("{:.2}", ratio), format!("{:.2}", ratio.log2())),
+                        None => ("-".to_string(), "-".to_string()),
+                    };
+                    writeln!(
+                        output,
+                        "{:<30} {:<14} {:>10} {:>12.3} {:>8} {:>9}",
+                        algorithm.name,
+                        distribution.name(),
+                        measurement.len,
+                        measurement.time.as_secs_f64() * 1000.0,
+                        ratio,
+                        exponent
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for DoublingExperiment {
+    /// From 1000 to 512000 elements, one trial each
+    fn default() -> Self {
+        Self::new(1000, 9, 1, SORT_SEED)
+    }
+}
+
+/// Time of one input size of a doubling experiment
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    pub len: usize,
+    pub time: Duration,
+    /// Time divided by the time of the previous size, `None` for the first size
+    pub ratio: Option<f64>,
+}
+
+impl Measurement {
+    /// Estimated b of the running time a N^b, as the ratio of doubling N is 2^b
+    pub fn exponent(&self) -> Option<f64> {
+        self.ratio.map(f64::log2)
+    }
+}
+
+fn ratio(time: Duration, previous: Duration) -> f64 {
+    // timers of some platforms round very short runs down to zero
+    time.as_secs_f64() / previous.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::benchmark::{algorithms, Distribution, DoublingExperiment, Measurement};
+    use crate::sort::order::is_sorted;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::time::Duration;
+
+    #[test]
+    fn distributions() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let sorted = Distribution::Sorted.generate(1000, &mut rng);
+        let reversed = Distribution::Reversed.generate(1000, &mut rng);
+        let organ_pipe = Distribution::OrganPipe.generate(6, &mut rng);
+        let few_unique = Distribution::FewUnique.generate(1000, &mut rng);
+        let nearly_sorted = Distribution::NearlySorted.generate(1000, &mut rng);
+
+        BooleanAssert::assert_that(is_sorted(&sorted)).is_true();
+        BooleanAssert::assert_that(reversed.windows(2).all(|pair| pair[0] > pair[1])).is_true();
+        ListAssert::assert_that(actual_vec(organ_pipe))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 2, 1, 0]))
+            .in_order();
+        BooleanAssert::assert_that(few_unique.iter().all(|value| *value < 8)).is_true();
+        NumericAssert::assert_that(actual(
+            nearly_sorted
+                .iter()
+                .enumerate()
+                .filter(|(index, value)| *index as u32 != **value)
+                .count(),
+        ))
+        .is_less_or_equal()
+        .to(expected(20));
+    }
+
+    #[test]
+    fn same_seed_same_inputs() {
+        for distribution in Distribution::ALL {
+            let first = distribution.generate(100, &mut StdRng::seed_from_u64(7));
+            let second = distribution.generate(100, &mut StdRng::seed_from_u64(7));
+
+            ListAssert::assert_that(actual_vec(first))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(second))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn every_algorithm_sorts_every_distribution() {
+        let experiment = DoublingExperiment::new(50, 3, 2, 42);
+
+        for algorithm in algorithms() {
+            for distribution in Distribution::ALL {
+                let lens: Vec<usize> = experiment
+                    .run(&algorithm, distribution)
+                    .iter()
+                    .map(|measurement| measurement.len)
+                    .collect();
+
+                ListAssert::assert_that(actual_vec(lens))
+                    .with_element_matcher(|a, b| a.eq(b))
+                    .is_equal_to(expected_vec(vec![50, 100, 200, 400]))
+                    .in_order();
+            }
+        }
+    }
+
+    #[test]
+    fn exponent() {
+        let measurement = Measurement {
+            len: 2000,
+            time: Duration::from_millis(400),
+            ratio: Some(4.0),
+        };
+
+        NumericAssert::assert_that(actual(measurement.exponent().unwrap_or(0.0)))
+            .is_equal()
+            .to(expected(2.0));
+    }
+
+    #[test]
+    fn run_all_prints_every_run() {
+        let mut output = Vec::new();
+
+        DoublingExperiment::new(16, 1, 1, 42)
+            .run_all(&algorithms()[..2], &mut output)
+            .unwrap();
+
+        // header and 2 sizes for 2 algorithms and 6 distributions
+        NumericAssert::assert_that(actual(String::from_utf8(output).unwrap().lines().count()))
+            .is_equal()
+            .to(expected(1 + 2 * 2 * 6));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::sort::insertion_sort;
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::merge_sort::merge_sorted;
+use std::cmp::Ordering;
+
+/// Runs of this size are sorted by insertion sort before merging starts
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
+where
+    T: Ord,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Stable: equal elements keep their original order
+///
+/// Merges runs of doubling width without recursion. The only allocation is one buffer
+/// which fits the first run of the widest merge.
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+/// Same as [`sort_by`], every comparison, swap and move goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    let len = array.len();
+    for run in array.chunks_mut(INSERTION_SORT_CUTOFF) {
+        insertion_sort::sort_with_operations(run, operations);
+    }
+    if len <= INSERTION_SORT_CUTOFF {
+        return;
+    }
+
+    let mut buffer: Vec<T> = Vec::with_capacity(widest_run(len));
+    let mut width = INSERTION_SORT_CUTOFF;
+    while width < len {
+        let mut low = 0;
+        while low + width < len {
+            let high = len.min(low + 2 * width);
+            let part = &mut array[low..high];
+            // runs which are already in order do not need to be merged
+            if operations.compare(&part[width], &part[width - 1]).is_lt() {
+                merge_sorted(part, width, &mut buffer, operations);
+            }
+            low = high;
+        }
+        width *= 2;
+    }
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+fn widest_run(len: usize) -> usize {
+    let mut width = INSERTION_SORT_CUTOFF;
+    while width * 2 < len {
+        width *= 2;
+    }
+
+    width
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::bottom_up_merge_sort::{sort, sort_by, sort_by_key, sort_slice};
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    #[test]
+    fn basics() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn longer_than_cutoff() {
+        let array: Vec<i32> = (0..1000).map(|value| (value * 7919) % 1009).collect();
+        let mut expected = array.clone();
+        expected.sort();
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected))
+            .in_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
+}
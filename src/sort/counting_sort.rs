@@ -0,0 +1,157 @@
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
+where
+    T: Copy + TryInto<usize>,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+/// Sorts small unsigned values, uses memory proportional to the greatest one
+///
+/// # Panics
+///
+/// Panics if a value does not fit in `usize`.
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Copy + TryInto<usize>,
+{
+    let key_range = array
+        .iter()
+        .map(|value| to_key(value) + 1)
+        .max()
+        .unwrap_or(0);
+
+    sort_by_key(array, key_range, to_key);
+}
+
+fn to_key<T>(value: &T) -> usize
+where
+    T: Copy + TryInto<usize>,
+{
+    (*value)
+        .try_into()
+        .unwrap_or_else(|_| panic!("value does not fit in usize"))
+}
+
+/// Stable key-indexed counting: every key must be less than `key_range`.
+///
+/// # Panics
+///
+/// Panics if `key` returns a value which is not less than `key_range`.
+pub fn sort_by_key<T, F>(array: &mut [T], key_range: usize, key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = array.iter().map(key).collect();
+    // count[key + 1] is the number of elements with the key, later turned into start positions
+    let mut count = vec![0; key_range + 1];
+    for key in &keys {
+        assert!(
+            *key < key_range,
+            "key {} is out of range {}",
+            key,
+            key_range
+        );
+        count[key + 1] += 1;
+    }
+    for key in 0..key_range {
+        count[key + 1] += count[key];
+    }
+    let mut destination: Vec<usize> = keys
+        .iter()
+        .map(|key| {
+            let position = count[*key];
+            count[*key] += 1;
+            position
+        })
+        .collect();
+
+    move_to_destination(array, &mut destination);
+}
+
+/// Moves every element to its index in `destination` by following permutation cycles,
+/// so no element has to be copied
+pub(crate) fn move_to_destination<T>(array: &mut [T], destination: &mut [usize]) {
+    for index in 0..array.len() {
+        while destination[index] != index {
+            let target = destination[index];
+            array.swap(index, target);
+            destination.swap(index, target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::counting_sort::{sort, sort_by_key, sort_slice};
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    #[test]
+    fn basics() {
+        let array: Vec<u8> = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array: Vec<u16> = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, 1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, 1]))
+            .in_order();
+    }
+
+    #[test]
+    fn wide_values() {
+        let mut array: Vec<u32> = vec![300, 2, 70_000, 2, 0, 1024];
+        let wide: Vec<u64> = vec![5, 1 << 12, 3];
+
+        sort_slice(&mut array);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 2, 2, 300, 1024, 70_000]))
+            .in_order();
+        ListAssert::assert_that(actual_vec(sort(wide)))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![3, 5, 1 << 12]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, 5, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_out_of_range() {
+        let mut array = vec![1, 5, 2];
+
+        sort_by_key(&mut array, 5, |value| *value);
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, 5, |(key, _)| *key as usize));
+    }
+}
@@ -0,0 +1,528 @@
+use crate::collection::simple_binary_heap::SimpleBinaryHeap;
+use crate::sort::merge_sort;
+use std::cmp::Ordering;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::mem;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Memory used for records by default before a chunk is spilled to disk
+const DEFAULT_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+/// Distinguishes spill directories of concurrent sorts within one process
+static NEXT_SORT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// How records are read from the input, written to the output and to spill files
+pub trait RecordFormat {
+    type Record;
+
+    /// Reads the next record, `None` at the end of the input
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+
+    fn write<W: Write>(&self, record: &Self::Record, writer: &mut W) -> io::Result<()>;
+
+    /// Memory taken by `record`, counted against the memory budget
+    fn memory_size(&self, _record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>()
+    }
+}
+
+/// Records are UTF-8 lines, written back terminated by `\n`
+pub struct LineFormat;
+
+impl RecordFormat for LineFormat {
+    type Record = String;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    fn write<W: Write>(&self, record: &String, writer: &mut W) -> io::Result<()> {
+        writer.write_all(record.as_bytes())?;
+        writer.write_all(b"\n")
+    }
+
+    fn memory_size(&self, record: &String) -> usize {
+        mem::size_of::<String>() + record.capacity()
+    }
+}
+
+/// Where and how much an external sort may keep in memory
+pub struct ExternalSortConfig {
+    pub(crate) memory_budget: usize,
+    pub(crate) temp_dir: PathBuf,
+}
+
+impl ExternalSortConfig {
+    /// Chunks are spilled to `temp_dir` once their records take more than `memory_budget` bytes
+    pub fn new(memory_budget: usize, temp_dir: PathBuf) -> ExternalSortConfig {
+        ExternalSortConfig {
+            memory_budget,
+            temp_dir,
+        }
+    }
+}
+
+impl Default for ExternalSortConfig {
+    /// 64 MiB in the system temporary directory
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_BUDGET, std::env::temp_dir())
+    }
+}
+
+/// Sorts records read from `input` into `output`, the input may be larger than memory
+pub fn sort<R, W, S>(input: R, output: W, format: &S, config: &ExternalSortConfig) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    S: RecordFormat,
+    S::Record: Ord,
+{
+    sort_by(input, output, format, S::Record::cmp, config)
+}
+
+/// Stable: equal records keep their original order
+///
+/// Reads chunks of records until they take up the memory budget, sorts every chunk by
+/// [`merge_sort`] and spills it to a temporary file. The spilled chunks are then merged
+/// at once through a [`SimpleBinaryHeap`] holding the smallest not yet written record of
+/// every chunk. Input which fits into the budget is sorted in memory only.
+pub fn sort_by<R, W, S, F>(
+    mut input: R,
+    mut output: W,
+    format: &S,
+    compare: F,
+    config: &ExternalSortConfig,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    S: RecordFormat,
+    F: Fn(&S::Record, &S::Record) -> Ordering,
+{
+    let mut spills = SpillFiles::new(config.temp_dir.clone());
+    loop {
+        let mut chunk = read_chunk(&mut input, format, config.memory_budget)?;
+        if chunk.is_empty() {
+            break;
+        }
+        merge_sort::sort_by(&mut chunk, &compare);
+        if spills.paths.is_empty() && is_exhausted(&mut input)? {
+            // everything fits into memory
+            write_all(&chunk, &mut output, format)?;
+            return output.flush();
+        }
+        let mut writer = BufWriter::new(spills.create()?);
+        write_all(&chunk, &mut writer, format)?;
+        writer.flush()?;
+    }
+
+    merge_spills(&spills, &mut output, format, &compare)?;
+    output.flush()
+}
+
+fn read_chunk<R, S>(input: &mut R, format: &S, memory_budget: usize) -> io::Result<Vec<S::Record>>
+where
+    R: BufRead,
+    S: RecordFormat,
+{
+    let mut chunk = Vec::new();
+    let mut used = 0;
+    // a chunk holds at least one record, whatever the budget
+    while chunk.is_empty() || used < memory_budget {
+        match format.read(input)? {
+            Some(record) => {
+                used += format.memory_size(&record);
+                chunk.push(record);
+            }
+            None => break,
+        }
+    }
+
+    Ok(chunk)
+}
+
+fn is_exhausted<R>(input: &mut R) -> io::Result<bool>
+where
+    R: BufRead,
+{
+    Ok(input.fill_buf()?.is_empty())
+}
+
+fn write_all<W, S>(records: &[S::Record], output: &mut W, format: &S) -> io::Result<()>
+where
+    W: Write,
+    S: RecordFormat,
+{
+    for record in records {
+        format.write(record, output)?;
+    }
+
+    Ok(())
+}
+
+/// k-way merge of the sorted spill files into `output`
+fn merge_spills<W, S, F>(
+    spills: &SpillFiles,
+    output: &mut W,
+    format: &S,
+    compare: &F,
+) -> io::Result<()>
+where
+    W: Write,
+    S: RecordFormat,
+    F: Fn(&S::Record, &S::Record) -> Ordering,
+{
+    let mut readers = Vec::with_capacity(spills.paths.len());
+    for path in &spills.paths {
+        readers.push(BufReader::new(File::open(path)?));
+    }
+
+    let mut heap = SimpleBinaryHeap::new();
+    for (chunk, reader) in readers.iter_mut().enumerate() {
+        if let Some(record) = format.read(reader)? {
+            heap.add(HeapEntry {
+                record,
+                chunk,
+                compare,
+            });
+        }
+    }
+    while let Some(smallest) = heap.delete_max() {
+        format.write(&smallest.record, output)?;
+        if let Some(record) = format.read(&mut readers[smallest.chunk])? {
+            heap.add(HeapEntry {
+                record,
+                chunk: smallest.chunk,
+                compare,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Next record of a spilled chunk. [`SimpleBinaryHeap`] is a max heap, so the order is reversed:
+/// the greatest entry has the smallest record, and from equal records the one of the earliest chunk.
+struct HeapEntry<'a, T, F> {
+    record: T,
+    chunk: usize,
+    compare: &'a F,
+}
+
+impl<'a, T, F> Ord for HeapEntry<'a, T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.compare)(&other.record, &self.record).then_with(|| other.chunk.cmp(&self.chunk))
+    }
+}
+
+impl<'a, T, F> PartialOrd for HeapEntry<'a, T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T, F> PartialEq for HeapEntry<'a, T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<'a, T, F> Eq for HeapEntry<'a, T, F> where F: Fn(&T, &T) -> Ordering {}
+
+/// Temporary files of one sort, removed when the sort finishes or fails.
+/// They live in a directory of their own which is created for this sort only,
+/// so no other process can have prepared a file under one of their names.
+struct SpillFiles {
+    temp_dir: PathBuf,
+    dir: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+impl SpillFiles {
+    fn new(temp_dir: PathBuf) -> SpillFiles {
+        SpillFiles {
+            temp_dir,
+            dir: None,
+            paths: Vec::new(),
+        }
+    }
+
+    fn create(&mut self) -> io::Result<File> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let dir = self.create_dir()?;
+                self.dir = Some(dir.clone());
+                dir
+            }
+        };
+        let path = dir.join(format!("chunk-{}.tmp", self.paths.len()));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        self.paths.push(path);
+
+        Ok(file)
+    }
+
+    /// Creates a directory which did not exist before, retrying with another name if it did
+    fn create_dir(&self) -> io::Result<PathBuf> {
+        let mut attempts = 0;
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.subsec_nanos());
+            let name = format!(
+                "external-sort-{}-{}-{}",
+                process::id(),
+                NEXT_SORT_ID.fetch_add(1, AtomicOrdering::Relaxed),
+                nanos
+            );
+            let dir = self.temp_dir.join(name);
+            match fs::create_dir(&dir) {
+                Ok(()) => return Ok(dir),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists && attempts < 16 => {
+                    attempts += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Drop for SpillFiles {
+    fn drop(&mut self) {
+        // nothing sensible can be done about a file which cannot be removed
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+        if let Some(dir) = &self.dir {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::external_sort::{sort, sort_by, ExternalSortConfig, LineFormat, RecordFormat};
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::fs;
+    use std::io::{self, BufRead, Write};
+    use std::path::PathBuf;
+
+    /// Empty directory for the spill files of one test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "external-sort-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    /// Checks that no spill file is left behind and removes the directory
+    fn assert_cleaned_up(dir: PathBuf) {
+        NumericAssert::assert_that(actual(fs::read_dir(&dir).unwrap().count()))
+            .is_equal()
+            .to(expected(0));
+        fs::remove_dir(dir).unwrap();
+    }
+
+    /// Records are little endian `u32`s, to check that the format is pluggable
+    struct U32Format;
+
+    impl RecordFormat for U32Format {
+        type Record = u32;
+
+        fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<u32>> {
+            let mut bytes = [0; 4];
+            match reader.read_exact(&mut bytes) {
+                Ok(()) => Ok(Some(u32::from_le_bytes(bytes))),
+                Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                Err(error) => Err(error),
+            }
+        }
+
+        fn write<W: Write>(&self, record: &u32, writer: &mut W) -> io::Result<()> {
+            writer.write_all(&record.to_le_bytes())
+        }
+    }
+
+    #[test]
+    fn basics_lines() {
+        let dir = temp_dir("lines");
+        let input = "pear\napple\nfig\nbanana\ncherry\napple\n";
+        let mut output = Vec::new();
+
+        sort(
+            input.as_bytes(),
+            &mut output,
+            &LineFormat,
+            &ExternalSortConfig::new(64, dir.clone()),
+        )
+        .unwrap();
+
+        ListAssert::assert_that(actual_vec(
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .map(String::from)
+                .collect(),
+        ))
+        .with_element_matcher(|a, b| a.eq(b))
+        .is_equal_to(expected_vec(
+            vec!["apple", "apple", "banana", "cherry", "fig", "pear"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        ))
+        .in_order();
+        assert_cleaned_up(dir);
+    }
+
+    #[test]
+    fn empty_input() {
+        let mut output = Vec::new();
+
+        sort(
+            "".as_bytes(),
+            &mut output,
+            &LineFormat,
+            &ExternalSortConfig::default(),
+        )
+        .unwrap();
+
+        NumericAssert::assert_that(actual(output.len()))
+            .is_equal()
+            .to(expected(0));
+    }
+
+    #[test]
+    fn many_chunks_of_binary_records() {
+        let dir = temp_dir("binary");
+        let mut rng = StdRng::seed_from_u64(42);
+        let values: Vec<u32> = (0..10_000).map(|_| rng.gen()).collect();
+        let input: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let mut output = Vec::new();
+
+        // 4 bytes per record, so about 40 chunks
+        sort(
+            input.as_slice(),
+            &mut output,
+            &U32Format,
+            &ExternalSortConfig::new(1000, dir.clone()),
+        )
+        .unwrap();
+
+        let mut expected_values = values;
+        expected_values.sort();
+        let sorted: Vec<u32> = output
+            .chunks(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected_values))
+            .in_order();
+        assert_cleaned_up(dir);
+    }
+
+    #[test]
+    fn stable_across_chunks() {
+        let dir = temp_dir("stable");
+        let input: String = (0..500)
+            .map(|index| format!("{}:{}\n", (index * 7) % 5, index))
+            .collect();
+        let mut output = Vec::new();
+        let key = |line: &String| line.split(':').next().unwrap().to_string();
+
+        sort_by(
+            input.as_bytes(),
+            &mut output,
+            &LineFormat,
+            |a, b| key(a).cmp(&key(b)),
+            &ExternalSortConfig::new(1000, dir.clone()),
+        )
+        .unwrap();
+
+        let mut expected_lines: Vec<String> = input.lines().map(String::from).collect();
+        expected_lines.sort_by_key(key);
+        ListAssert::assert_that(actual_vec(
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .map(String::from)
+                .collect(),
+        ))
+        .with_element_matcher(|a, b| a.eq(b))
+        .is_equal_to(expected_vec(expected_lines))
+        .in_order();
+        assert_cleaned_up(dir);
+    }
+
+    #[test]
+    fn leaves_other_files_in_temp_dir_alone() {
+        let dir = temp_dir("other-files");
+        let other = dir.join("chunk-0.tmp");
+        fs::write(&other, "not a spill file").unwrap();
+        let input: String = (0..500)
+            .rev()
+            .map(|index| format!("{index:03}\n"))
+            .collect();
+        let mut output = Vec::new();
+
+        sort(
+            input.as_bytes(),
+            &mut output,
+            &LineFormat,
+            &ExternalSortConfig::new(1000, dir.clone()),
+        )
+        .unwrap();
+
+        let expected_output: String = (0..500).map(|index| format!("{index:03}\n")).collect();
+        ListAssert::assert_that(actual_vec(output))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected_output.into_bytes()))
+            .in_order();
+        ListAssert::assert_that(actual_vec(fs::read(&other).unwrap()))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(b"not a spill file".to_vec()))
+            .in_order();
+        fs::remove_file(other).unwrap();
+        assert_cleaned_up(dir);
+    }
+}
@@ -1,9 +1,148 @@
-use crate::collection::simple_binary_heap::SimpleBinaryHeap;
-use std::fmt::Debug;
+use crate::collection::d_ary_heap;
+use crate::sort::instrumentation::SortOperations;
+use std::cmp::Ordering;
 
-pub fn sort<T>(array: Vec<T>) -> Vec<T>
+/// Heap sort on a heap with `arity` children per node, 2 is the classic binary heap.
+/// Wider heaps are shallower: fewer swaps, more comparisons per level.
+///
+/// # Panics
+///
+/// If `arity` is less than 2
+pub fn sort<T>(mut array: Vec<T>, arity: usize) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    SimpleBinaryHeap::from(array).sort()
+    sort_slice(&mut array, arity);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T], arity: usize)
+where
+    T: Ord,
+{
+    sort_by(array, arity, T::cmp)
+}
+
+/// Not stable: heap order does not keep track of the original positions
+pub fn sort_by<T, F>(array: &mut [T], arity: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, arity, &mut compare)
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], arity: usize, mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, arity, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison and swap goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], arity: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    d_ary_heap::sort_slice_with_operations(array, arity, operations)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::heap_sort;
+    use crate::sort::instrumentation::Instrumented;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn basics() {
+        for arity in [2, 4, 8] {
+            let array = vec!["S", "O", "R", "T", "E", "X", "A", "M", "P", "L", "E"];
+
+            let sorted = heap_sort::sort(array, arity);
+
+            ListAssert::assert_that(actual_vec(sorted))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(vec![
+                    "A", "E", "E", "L", "M", "O", "P", "R", "S", "T", "X",
+                ]))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let mut array = vec![(3, 'c'), (1, 'a'), (2, 'b'), (5, 'e'), (4, 'd')];
+
+        heap_sort::sort_by_key(&mut array, 4, |(key, _)| *key);
+
+        let letters: Vec<char> = array.iter().map(|(_, letter)| *letter).collect();
+        ListAssert::assert_that(actual_vec(letters))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!['a', 'b', 'c', 'd', 'e']))
+            .in_order();
+    }
+
+    #[test]
+    fn sorts_with_any_arity() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for arity in 2..10 {
+            let mut array: Vec<u32> = (0..1000).map(|_| rng.gen_range(0..100)).collect();
+            let mut expected_array = array.clone();
+            expected_array.sort();
+
+            heap_sort::sort_slice(&mut array, arity);
+
+            ListAssert::assert_that(actual_vec(array))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(expected_array))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn wider_heap_swaps_less() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let array: Vec<u32> = (0..10_000).map(|_| rng.gen()).collect();
+        let swaps = |arity| {
+            let mut array = array.clone();
+            Instrumented::new(u32::cmp)
+                .run(&mut array, |array, operations| {
+                    heap_sort::sort_with_operations(array, arity, operations)
+                })
+                .swaps
+        };
+
+        NumericAssert::assert_that(actual(swaps(4)))
+            .is_less()
+            .than(expected(swaps(2)));
+        NumericAssert::assert_that(actual(swaps(8)))
+            .is_less()
+            .than(expected(swaps(4)));
+    }
+
+    #[test]
+    fn swap_trace_sorts() {
+        let original = vec![5, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3];
+        let mut array = original.clone();
+        let mut swaps = Vec::new();
+
+        Instrumented::with_swap_callback(i32::cmp, |i, j| swaps.push((i, j)))
+            .run(&mut array, |array, operations| {
+                heap_sort::sort_with_operations(array, 8, operations)
+            });
+
+        let mut replayed = original;
+        for (i, j) in swaps {
+            replayed.swap(i, j);
+        }
+        ListAssert::assert_that(actual_vec(replayed))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(array))
+            .in_order();
+    }
 }
@@ -1,27 +1,59 @@
-use std::fmt::Debug;
+use crate::sort::instrumentation::SortOperations;
+use std::cmp::Ordering;
 
 pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    for current_index in 1..array.len() {
-        move_to_ordered_position(&mut array, current_index);
-    }
+    sort_slice(&mut array);
 
     array
 }
 
-fn move_to_ordered_position<T>(array: &mut [T], current_index: usize)
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Stable: an element never moves past an equal one
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison and swap goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
 where
-    T: Ord + Debug,
+    O: SortOperations<T>,
+{
+    for current_index in 1..array.len() {
+        move_to_ordered_position(array, current_index, operations);
+    }
+}
+
+fn move_to_ordered_position<T, O>(array: &mut [T], current_index: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
 {
     let mut current_index = current_index;
     let mut previous_index = current_index - 1;
     loop {
         let current = &array[current_index];
         let previous = &array[previous_index];
-        if current.lt(previous) {
-            array.swap(current_index, previous_index);
+        if operations.compare(current, previous).is_lt() {
+            operations.swap(array, current_index, previous_index);
             if previous_index == 0 {
                 break;
             }
@@ -35,7 +67,8 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::sort::insertion_sort::sort;
+    use crate::sort::insertion_sort::{sort, sort_by, sort_by_key, sort_slice};
+    use crate::sort::test_utils::assert_stable;
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
 
@@ -50,4 +83,45 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
 }
@@ -0,0 +1,245 @@
+pub use crate::operations::SortOperations;
+use std::cmp::Ordering;
+use std::mem;
+
+/// Number of operations done by a sort
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub moves: usize,
+}
+
+impl SortStats {
+    /// Every comparison reads two entries, every swap reads and writes two entries
+    /// and every move reads and writes one
+    pub fn array_accesses(&self) -> usize {
+        2 * self.comparisons + 4 * self.swaps + 2 * self.moves
+    }
+}
+
+type Compare<'a, T> = Box<dyn FnMut(&T, &T) -> Ordering + 'a>;
+
+/// Comparator which counts what the sort does and optionally reports every swap
+pub struct Instrumented<'a, T> {
+    compare: Compare<'a, T>,
+    on_swap: Option<Box<dyn FnMut(usize, usize) + 'a>>,
+    // address and length of the whole array, sorts recurse into subslices
+    // but swaps are reported against it
+    base: Option<(usize, usize)>,
+    stats: SortStats,
+}
+
+impl<'a, T> Instrumented<'a, T> {
+    pub fn new(compare: impl FnMut(&T, &T) -> Ordering + 'a) -> Instrumented<'a, T> {
+        Instrumented {
+            compare: Box::new(compare),
+            on_swap: None,
+            base: None,
+            stats: SortStats::default(),
+        }
+    }
+
+    /// `on_swap` gets the indices of every swap, relative to the array given to [`Instrumented::run`]
+    pub fn with_swap_callback(
+        compare: impl FnMut(&T, &T) -> Ordering + 'a,
+        on_swap: impl FnMut(usize, usize) + 'a,
+    ) -> Instrumented<'a, T> {
+        Instrumented {
+            compare: Box::new(compare),
+            on_swap: Some(Box::new(on_swap)),
+            base: None,
+            stats: SortStats::default(),
+        }
+    }
+
+    /// Runs `sort` on `array`, e.g. `instrumented.run(&mut array, shell_sort::sort_with_operations)`
+    pub fn run<F>(&mut self, array: &mut [T], sort: F) -> SortStats
+    where
+        F: FnOnce(&mut [T], &mut Self),
+    {
+        self.base = Some((array.as_ptr() as usize, array.len()));
+        sort(array, self);
+        self.base = None;
+
+        self.stats
+    }
+
+    pub fn stats(&self) -> SortStats {
+        self.stats
+    }
+
+    /// Index of the first element of `array` in the array given to [`Instrumented::run`]
+    ///
+    /// # Panics
+    ///
+    /// If `array` is not a part of that array
+    fn offset_of(&self, array: &[T]) -> usize {
+        let Some((base, len)) = self.base else {
+            return 0;
+        };
+        (array.as_ptr() as usize)
+            .checked_sub(base)
+            .map(|bytes| bytes / mem::size_of::<T>().max(1))
+            .filter(|offset| offset + array.len() <= len)
+            .expect("swapped slice is not a part of the array being sorted")
+    }
+}
+
+impl<'a, T> SortOperations<T> for Instrumented<'a, T> {
+    fn compare(&mut self, first: &T, second: &T) -> Ordering {
+        self.stats.comparisons += 1;
+        (self.compare)(first, second)
+    }
+
+    fn swap(&mut self, array: &mut [T], first: usize, second: usize) {
+        self.stats.swaps += 1;
+        array.swap(first, second);
+        let offset = self.offset_of(array);
+        if let Some(on_swap) = self.on_swap.as_mut() {
+            on_swap(offset + first, offset + second);
+        }
+    }
+
+    fn moved(&mut self, count: usize) {
+        self.stats.moves += count;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::instrumentation::{Instrumented, SortOperations, SortStats};
+    use crate::sort::{insertion_sort, quick_sort, selection_sort, shell_sort};
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    const N: usize = 1000;
+
+    fn shuffled(len: usize) -> Vec<usize> {
+        let mut array: Vec<usize> = (0..len).collect();
+        array.shuffle(&mut StdRng::seed_from_u64(42));
+
+        array
+    }
+
+    #[test]
+    fn selection_sort_compares_n_squared_half() {
+        let mut array = shuffled(N);
+
+        let stats =
+            Instrumented::new(usize::cmp).run(&mut array, selection_sort::sort_with_operations);
+
+        NumericAssert::assert_that(actual(stats.comparisons))
+            .is_equal()
+            .to(expected(N * (N - 1) / 2));
+        NumericAssert::assert_that(actual(stats.swaps))
+            .is_less_or_equal()
+            .to(expected(N));
+    }
+
+    #[test]
+    fn selection_sort_on_sorted_swaps_nothing() {
+        let mut array: Vec<usize> = (0..N).collect();
+
+        let stats =
+            Instrumented::new(usize::cmp).run(&mut array, selection_sort::sort_with_operations);
+
+        NumericAssert::assert_that(actual(stats.swaps))
+            .is_equal()
+            .to(expected(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_outside_of_the_sorted_array() {
+        let mut array = vec![1, 2, 3];
+        let mut other = vec![4, 5];
+
+        Instrumented::new(i32::cmp).run(&mut array, |_, operations| {
+            operations.swap(&mut other, 0, 1)
+        });
+    }
+
+    #[test]
+    fn insertion_sort_on_reversed_compares_and_swaps_n_squared_half() {
+        let mut array: Vec<usize> = (0..N).rev().collect();
+
+        let stats =
+            Instrumented::new(usize::cmp).run(&mut array, insertion_sort::sort_with_operations);
+
+        NumericAssert::assert_that(actual(stats.comparisons))
+            .is_equal()
+            .to(expected(N * (N - 1) / 2));
+        NumericAssert::assert_that(actual(stats.swaps))
+            .is_equal()
+            .to(expected(N * (N - 1) / 2));
+    }
+
+    #[test]
+    fn quick_sort_compares_about_1_39_n_lg_n() {
+        let len = 100_000;
+        let mut array = shuffled(len);
+
+        let stats = Instrumented::new(usize::cmp).run(&mut array, |array, operations| {
+            quick_sort::sort_with_operations(array, operations, &mut StdRng::seed_from_u64(7))
+        });
+
+        let expected_comparisons = 1.39 * len as f64 * (len as f64).log2();
+        let ratio = stats.comparisons as f64 / expected_comparisons;
+        BooleanAssert::assert_that(ratio > 0.7 && ratio < 1.1).is_true();
+    }
+
+    #[test]
+    fn shell_sort_does_fewer_comparisons_than_insertion_sort() {
+        let mut array = shuffled(N);
+        let shell = Instrumented::new(usize::cmp).run(&mut array, shell_sort::sort_with_operations);
+        let mut array = shuffled(N);
+        let insertion =
+            Instrumented::new(usize::cmp).run(&mut array, insertion_sort::sort_with_operations);
+
+        NumericAssert::assert_that(actual(shell.comparisons))
+            .is_less()
+            .than(expected(insertion.comparisons / 4));
+    }
+
+    #[test]
+    fn replaying_swaps_sorts_a_copy() {
+        let original = shuffled(200);
+        let mut array = original.clone();
+        let mut swaps = Vec::new();
+
+        Instrumented::with_swap_callback(usize::cmp, |first, second| swaps.push((first, second)))
+            .run(&mut array, |array, operations| {
+                quick_sort::sort_with_operations(array, operations, &mut StdRng::seed_from_u64(1))
+            });
+
+        // the shuffle before partitioning is not a comparison sort swap, so replay it too
+        let mut replayed = original;
+        crate::sort::shuffle_sort::shuffle_with(&mut replayed, &mut StdRng::seed_from_u64(1));
+        for (first, second) in swaps {
+            replayed.swap(first, second);
+        }
+        ListAssert::assert_that(actual_vec(replayed))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec((0..200).collect()))
+            .in_order();
+    }
+
+    #[test]
+    fn array_accesses() {
+        let stats = SortStats {
+            comparisons: 3,
+            swaps: 2,
+            moves: 5,
+        };
+
+        NumericAssert::assert_that(actual(stats.array_accesses()))
+            .is_equal()
+            .to(expected(24));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::quick_sort::partition;
+use crate::sort::{heap_sort, insertion_sort};
+use std::cmp::Ordering;
+
+/// Parts of this size are finished by insertion sort
+const INSERTION_SORT_CUTOFF: usize = 16;
+/// From this size the pivot is a ninther instead of a median of three
+const NINTHER_CUTOFF: usize = 128;
+
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
+where
+    T: Ord,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Not stable: partitioning moves elements past equal ones
+///
+/// Quick sort without shuffling: the pivot is a median of three (or a ninther for long parts)
+/// and once recursion gets deeper than `2 * lg N` the part is finished by heap sort,
+/// so the worst case stays O(N lg N).
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison and swap goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    let depth_limit = 2 * (usize::BITS - array.len().leading_zeros()) as usize;
+
+    sort_part(array, depth_limit, operations);
+}
+
+fn sort_part<T, O>(array: &mut [T], depth_limit: usize, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    if array.len() <= INSERTION_SORT_CUTOFF {
+        insertion_sort::sort_with_operations(array, operations);
+        return;
+    }
+    if depth_limit == 0 {
+        heap_sort::sort_with_operations(array, 2, operations);
+        return;
+    }
+
+    let pivot_index = choose_pivot(array, operations);
+    operations.swap(array, 0, pivot_index);
+    let elem_at_place = partition(array, operations);
+    let (lower, greater) = array.split_at_mut(elem_at_place);
+    sort_part(lower, depth_limit - 1, operations);
+    sort_part(&mut greater[1..], depth_limit - 1, operations);
+}
+
+pub(crate) fn choose_pivot<T, O>(array: &[T], operations: &mut O) -> usize
+where
+    O: SortOperations<T>,
+{
+    let last = array.len() - 1;
+    let middle = array.len() / 2;
+    if array.len() < NINTHER_CUTOFF {
+        return median_of_three(array, 0, middle, last, operations);
+    }
+
+    let step = array.len() / 8;
+    let first = median_of_three(array, 0, step, 2 * step, operations);
+    let second = median_of_three(array, middle - step, middle, middle + step, operations);
+    let third = median_of_three(array, last - 2 * step, last - step, last, operations);
+
+    median_of_three(array, first, second, third, operations)
+}
+
+fn median_of_three<T, O>(
+    array: &[T],
+    first: usize,
+    second: usize,
+    third: usize,
+    operations: &mut O,
+) -> usize
+where
+    O: SortOperations<T>,
+{
+    let (first_value, second_value, third_value) = (&array[first], &array[second], &array[third]);
+    if operations.compare(first_value, second_value).is_lt() {
+        if operations.compare(second_value, third_value).is_lt() {
+            second
+        } else if operations.compare(first_value, third_value).is_lt() {
+            third
+        } else {
+            first
+        }
+    } else if operations.compare(first_value, third_value).is_lt() {
+        first
+    } else if operations.compare(second_value, third_value).is_lt() {
+        third
+    } else {
+        second
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::intro_sort::{sort, sort_by, sort_by_key, sort_part, sort_slice};
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    #[test]
+    fn basics() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn empty() {
+        let sorted: Vec<i32> = sort(Vec::new());
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(Vec::new()))
+            .in_order();
+    }
+
+    #[test]
+    fn compares_n_lg_n_on_patterns() {
+        let len: usize = 4096;
+        let patterns: Vec<Vec<usize>> = vec![
+            (0..len).collect(),
+            (0..len).rev().collect(),
+            vec![7; len],
+            (0..len).map(|value| value.min(len - value)).collect(),
+            (0..len).map(|value| value % 4).collect(),
+        ];
+
+        for array in patterns {
+            let mut expected = array.clone();
+            expected.sort();
+            let mut sorted = array;
+            let mut comparisons = 0;
+
+            sort_by(&mut sorted, |a, b| {
+                comparisons += 1;
+                a.cmp(b)
+            });
+
+            BooleanAssert::assert_that(sorted == expected).is_true();
+            // lg 4096 = 12
+            BooleanAssert::assert_that(comparisons < 4 * len * 12).is_true();
+        }
+    }
+
+    #[test]
+    fn falls_back_to_heap_sort() {
+        let mut array: Vec<i32> = (0..100).map(|value| (value * 37) % 101).collect();
+        let mut expected = array.clone();
+        expected.sort();
+
+        sort_part(&mut array, 0, &mut i32::cmp);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected))
+            .in_order();
+    }
+}
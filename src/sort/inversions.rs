@@ -0,0 +1,218 @@
+//! Counting pairs of elements which are out of order
+use crate::sort::merge_sort::merge_sorted;
+use crate::AlgoError;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Number of pairs `i < j` with `array[i] > array[j]`, equal elements are not an inversion
+pub fn count_inversions<T>(array: &[T]) -> usize
+where
+    T: Ord,
+{
+    count_inversions_by(array, T::cmp)
+}
+
+/// Same as [`count_inversions`] according to `compare`.
+///
+/// Merge sorts references to the elements, so the array itself is not touched. Before
+/// two sorted halves are merged, the elements of the first half greater than each element
+/// of the second half are counted in one pass, so it takes time proportional to N lg N.
+pub fn count_inversions_by<T, F>(array: &[T], mut compare: F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut references: Vec<&T> = array.iter().collect();
+    let mut buffer = Vec::with_capacity(array.len() / 2);
+
+    count_part(
+        &mut references,
+        &mut buffer,
+        &mut |first: &&T, second: &&T| compare(first, second),
+    )
+}
+
+/// Number of pairs ranked in different order by `first` and `second`, which have to hold
+/// the same distinct elements. 0 for equal rankings, N(N-1)/2 for reversed ones.
+///
+/// Returns [`AlgoError::InvalidArgument`] if the rankings differ in length, hold
+/// different elements or repeat an element.
+pub fn kendall_tau_distance<T>(first: &[T], second: &[T]) -> Result<usize, AlgoError>
+where
+    T: Eq + Hash,
+{
+    if first.len() != second.len() {
+        return Err(AlgoError::invalid_argument(
+            "second ranking length",
+            &second.len(),
+            &format!("differs from the first ranking length {}", first.len()),
+        ));
+    }
+    let mut rank_in_second: HashMap<&T, usize> = HashMap::with_capacity(second.len());
+    for (rank, element) in second.iter().enumerate() {
+        if rank_in_second.insert(element, rank).is_some() {
+            return Err(AlgoError::invalid_argument(
+                "second ranking position",
+                &rank,
+                "repeats an earlier element",
+            ));
+        }
+    }
+    // every rank is taken exactly once when both rankings hold the same distinct elements
+    let mut seen = vec![false; second.len()];
+    let mut ranks = Vec::with_capacity(first.len());
+    for (position, element) in first.iter().enumerate() {
+        let Some(&rank) = rank_in_second.get(element) else {
+            return Err(AlgoError::invalid_argument(
+                "first ranking position",
+                &position,
+                "holds an element missing from the second ranking",
+            ));
+        };
+        if seen[rank] {
+            return Err(AlgoError::invalid_argument(
+                "first ranking position",
+                &position,
+                "repeats an earlier element",
+            ));
+        }
+        seen[rank] = true;
+        ranks.push(rank);
+    }
+
+    Ok(count_inversions(&ranks))
+}
+
+fn count_part<T, F>(array: &mut [T], buffer: &mut Vec<T>, compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if array.len() <= 1 {
+        return 0;
+    }
+    let middle = array.len() / 2;
+    let (first_part, second_part) = array.split_at_mut(middle);
+    let mut inversions = count_part(first_part, buffer, compare);
+    inversions += count_part(second_part, buffer, compare);
+
+    // elements of the first part before `first_index` are not greater than the current second one
+    let mut first_index = 0;
+    for second in second_part.iter() {
+        while first_index < middle && !compare(second, &first_part[first_index]).is_lt() {
+            first_index += 1;
+        }
+        inversions += middle - first_index;
+    }
+
+    merge_sorted(array, middle, buffer, compare);
+
+    inversions
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::inversions::{count_inversions, count_inversions_by, kendall_tau_distance};
+    use crate::AlgoError;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn count_inversions_quadratic(array: &[u8]) -> usize {
+        let mut inversions = 0;
+        for i in 0..array.len() {
+            for j in i + 1..array.len() {
+                if array[i] > array[j] {
+                    inversions += 1;
+                }
+            }
+        }
+
+        inversions
+    }
+
+    #[test]
+    fn basics() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let inversions = count_inversions(&array);
+
+        NumericAssert::assert_that(actual(inversions))
+            .is_equal()
+            .to(expected(12));
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![3, 2, 3, 4, 1, 6, 9, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn sorted_and_reversed() {
+        let sorted: Vec<u32> = (0..100).collect();
+        let reversed: Vec<u32> = (0..100).rev().collect();
+
+        NumericAssert::assert_that(actual(count_inversions(&sorted)))
+            .is_equal()
+            .to(expected(0));
+        NumericAssert::assert_that(actual(count_inversions(&reversed)))
+            .is_equal()
+            .to(expected(100 * 99 / 2));
+        NumericAssert::assert_that(actual(count_inversions_by(&reversed, |a, b| b.cmp(a))))
+            .is_equal()
+            .to(expected(0));
+    }
+
+    #[test]
+    fn same_as_quadratic() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for len in 0..200 {
+            let array: Vec<u8> = (0..len).map(|_| rng.gen_range(0..10)).collect();
+
+            NumericAssert::assert_that(actual(count_inversions(&array)))
+                .is_equal()
+                .to(expected(count_inversions_quadratic(&array)));
+        }
+    }
+
+    #[test]
+    fn basics_kendall_tau_distance() {
+        let first = vec!["a", "b", "c", "d", "e"];
+        let second = vec!["c", "d", "a", "b", "e"];
+        let reversed = vec!["e", "d", "c", "b", "a"];
+
+        NumericAssert::assert_that(actual(kendall_tau_distance(&first, &second).unwrap()))
+            .is_equal()
+            .to(expected(4));
+        NumericAssert::assert_that(actual(kendall_tau_distance(&second, &first).unwrap()))
+            .is_equal()
+            .to(expected(4));
+        NumericAssert::assert_that(actual(kendall_tau_distance(&first, &first).unwrap()))
+            .is_equal()
+            .to(expected(0));
+        NumericAssert::assert_that(actual(kendall_tau_distance(&first, &reversed).unwrap()))
+            .is_equal()
+            .to(expected(10));
+    }
+
+    #[test]
+    fn kendall_tau_distance_of_different_rankings() {
+        let different = kendall_tau_distance(&[1, 2, 3], &[1, 2, 4]);
+        let repeated = kendall_tau_distance(&[1, 2, 2], &[1, 2, 2]);
+        let repeated_in_first = kendall_tau_distance(&[1, 1, 2], &[1, 2, 3]);
+        let missing_from_first = kendall_tau_distance(&[2, 3, 3], &[3, 2, 1]);
+        let shorter = kendall_tau_distance(&[1, 2, 3], &[1, 2]);
+
+        for result in [
+            different,
+            repeated,
+            repeated_in_first,
+            missing_from_first,
+            shorter,
+        ] {
+            BooleanAssert::assert_that(matches!(result, Err(AlgoError::InvalidArgument(_))))
+                .is_true();
+        }
+    }
+}
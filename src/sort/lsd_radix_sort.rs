@@ -0,0 +1,136 @@
+use crate::sort::counting_sort;
+
+/// Number of different values of one byte
+const RADIX: usize = 256;
+
+/// Unsigned integer which can be sorted byte by byte
+pub trait RadixKey: Copy {
+    /// Number of bytes in the key
+    const BYTES: usize;
+
+    /// Byte at `index`, counting from the least significant one
+    fn byte_at(&self, index: usize) -> u8;
+}
+
+macro_rules! radix_key {
+    ($($key:ty),*) => {
+        $(
+            impl RadixKey for $key {
+                const BYTES: usize = std::mem::size_of::<$key>();
+
+                fn byte_at(&self, index: usize) -> u8 {
+                    (*self >> (8 * index)) as u8
+                }
+            }
+        )*
+    };
+}
+
+radix_key!(u8, u16, u32, u64, u128, usize);
+
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
+where
+    T: RadixKey,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: RadixKey,
+{
+    sort_by_key(array, |value| *value);
+}
+
+/// Stable: every pass is a stable counting sort by one byte of the key,
+/// starting from the least significant one
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: RadixKey,
+    F: FnMut(&T) -> K,
+{
+    for byte in 0..K::BYTES {
+        counting_sort::sort_by_key(array, RADIX, |value| key(value).byte_at(byte) as usize);
+    }
+}
+
+/// Sorts byte strings which all have exactly `width` bytes, e.g. fixed length ASCII codes.
+///
+/// # Panics
+///
+/// Panics if any string is shorter than `width`.
+pub fn sort_fixed_width<S>(array: &mut [S], width: usize)
+where
+    S: AsRef<[u8]>,
+{
+    for byte in (0..width).rev() {
+        counting_sort::sort_by_key(array, RADIX, |value| value.as_ref()[byte] as usize);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::lsd_radix_sort::{sort, sort_by_key, sort_fixed_width, sort_slice};
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    #[test]
+    fn basics() {
+        let array: Vec<u32> = vec![3, 2, 3_000_000, 4, 1, 65_536, 9, 0];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 4, 9, 65_536, 3_000_000]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array: Vec<u64> = vec![7, 3, 2, 3, 4, 1, 6, u64::MAX, 0, 1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, u64::MAX, 1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_fixed_width() {
+        let mut array = vec![
+            "4PGC938", "2IYE230", "3CIO720", "1ICK750", "1OHV845", "4JZY524",
+        ];
+
+        sort_fixed_width(&mut array, 7);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "1ICK750", "1OHV845", "2IYE230", "3CIO720", "4JZY524", "4PGC938",
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
+}
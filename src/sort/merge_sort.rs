@@ -1,81 +1,203 @@
-use std::fmt::Debug;
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::parallel::ParallelConfig;
+use std::cmp::Ordering;
+use std::{ptr, thread};
 
-pub fn sort<T>(array: Vec<T>) -> Vec<T>
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    if array.is_empty() || array.len() == 1 {
-        return array;
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Stable: equal elements keep their original order
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+/// Same as [`sort_by`], every comparison and move goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    if array.len() <= 1 {
+        return;
     }
-    //Usual implementation requires copy, because it is not possible to extract value from array, we converting it to optional,
-    // so when we will take value from it, it will left None
-    let array = array.into_iter().map(|val| Some(val)).collect();
-
-    sort_no_copy(array)
-        .into_iter()
-        .map(|val| val.expect("None should not be present in the result array"))
-        .collect()
+    // Only the first half of every merged part is moved out, so one buffer of half size
+    // is enough for the whole sort
+    let mut buffer: Vec<T> = Vec::with_capacity(array.len() / 2);
+
+    sort_part(array, &mut buffer, operations);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+pub fn par_sort<T>(mut array: Vec<T>, config: &ParallelConfig) -> Vec<T>
+where
+    T: Ord + Send,
+{
+    par_sort_slice(&mut array, config);
+
+    array
+}
+
+pub fn par_sort_slice<T>(array: &mut [T], config: &ParallelConfig)
+where
+    T: Ord + Send,
+{
+    par_sort_by(array, T::cmp, config);
 }
 
-fn sort_no_copy<T>(mut array: Vec<Option<T>>) -> Vec<Option<T>>
+/// Same result as [`sort_by`]: both halves are sorted on different threads
+/// until parts get shorter than the sequential cutoff or threads run out
+pub fn par_sort_by<T, F>(array: &mut [T], compare: F, config: &ParallelConfig)
 where
-    T: Ord + Debug,
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
 {
-    if array.is_empty() || array.len() == 1 {
-        return array;
+    par_sort_part(array, &compare, config.threads, config.sequential_cutoff);
+}
+
+fn par_sort_part<T, F>(array: &mut [T], compare: &F, threads: usize, sequential_cutoff: usize)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if threads <= 1 || array.len() <= sequential_cutoff.max(1) {
+        sort_by(array, compare);
+        return;
     }
     let middle = array.len() / 2;
-    let second = array.split_off(middle);
-    let sorted_first_part = sort_no_copy(array);
-    let sorted_second_part = sort_no_copy(second);
+    let (first_part, second_part) = array.split_at_mut(middle);
+    thread::scope(|scope| {
+        scope.spawn(|| par_sort_part(first_part, compare, threads / 2, sequential_cutoff));
+        par_sort_part(
+            second_part,
+            compare,
+            threads - threads / 2,
+            sequential_cutoff,
+        );
+    });
 
-    merge_sorted(sorted_first_part, sorted_second_part)
+    let mut buffer: Vec<T> = Vec::with_capacity(middle);
+    merge_sorted(array, middle, &mut buffer, &mut &compare);
 }
 
-fn merge_sorted<T>(
-    mut first_array: Vec<Option<T>>,
-    mut second_array: Vec<Option<T>>,
-) -> Vec<Option<T>>
+fn sort_part<T, O>(array: &mut [T], buffer: &mut Vec<T>, operations: &mut O)
 where
-    T: Ord + Debug,
+    O: SortOperations<T>,
 {
-    let mut merged: Vec<Option<T>> = Vec::with_capacity(first_array.len() + second_array.len());
-    let mut first_index: usize = 0;
-    let mut second_index: usize = 0;
-
-    while first_index < first_array.len() || second_index < second_array.len() {
-        let first_value = first_array.get_mut(first_index);
-        let second_value = second_array.get_mut(second_index);
-        match (first_value, second_value) {
-            (Some(first), Some(second)) => {
-                if first.as_ref().unwrap().lt(second.as_ref().unwrap()) {
-                    merged.push(first.take());
-                    first_index += 1;
-                } else {
-                    merged.push(second.take());
-                    second_index += 1;
-                }
-            }
-            (Some(value), None) => {
-                first_index += 1;
-                merged.push(value.take());
-            }
-            (None, Some(value)) => {
+    if array.len() <= 1 {
+        return;
+    }
+    let middle = array.len() / 2;
+    let (first_part, second_part) = array.split_at_mut(middle);
+    sort_part(first_part, buffer, operations);
+    sort_part(second_part, buffer, operations);
+
+    merge_sorted(array, middle, buffer, operations);
+}
+
+/// Merges already sorted `array[..middle]` and `array[middle..]` in place.
+///
+/// Usual implementation requires copy, because it is not possible to move a value out of a slice.
+/// Instead, the first part is moved into the spare capacity of `buffer` and then merged back
+/// together with the second part. [`MergeHole`] owns the elements which are still in the buffer,
+/// so they are moved back to the array even if a comparison panics.
+pub(crate) fn merge_sorted<T, O>(
+    array: &mut [T],
+    middle: usize,
+    buffer: &mut Vec<T>,
+    operations: &mut O,
+) where
+    O: SortOperations<T>,
+{
+    // buffer never holds initialised elements, so this guarantees capacity of at least `middle`
+    buffer.reserve(middle);
+    let len = array.len();
+    // the first part goes to the buffer and back, the second one is moved once
+    operations.moved(middle + len);
+    unsafe {
+        let array_ptr = array.as_mut_ptr();
+        ptr::copy_nonoverlapping(array_ptr, buffer.as_mut_ptr(), middle);
+
+        let mut hole = MergeHole {
+            buffer: buffer.as_ptr(),
+            first_index: 0,
+            first_end: middle,
+            array: array_ptr,
+            destination: 0,
+        };
+        let mut second_index = middle;
+        while hole.first_index < hole.first_end && second_index < len {
+            let first = &*hole.buffer.add(hole.first_index);
+            let second = &*array_ptr.add(second_index);
+            // taking the first part on equality keeps the sort stable
+            let source = if operations.compare(second, first).is_lt() {
                 second_index += 1;
-                merged.push(value.take());
-            }
-            (None, None) => break,
+                second
+            } else {
+                hole.first_index += 1;
+                first
+            };
+            ptr::copy_nonoverlapping(source, array_ptr.add(hole.destination), 1);
+            hole.destination += 1;
         }
     }
+}
 
-    merged
+/// Elements of the first part which are not yet merged. They always fit exactly into the gap
+/// between `destination` and the first not merged element of the second part.
+pub(crate) struct MergeHole<T> {
+    pub(crate) buffer: *const T,
+    pub(crate) first_index: usize,
+    pub(crate) first_end: usize,
+    pub(crate) array: *mut T,
+    pub(crate) destination: usize,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buffer.add(self.first_index),
+                self.array.add(self.destination),
+                self.first_end - self.first_index,
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::sort::merge_sort::sort;
+    use crate::sort::merge_sort::{
+        par_sort, par_sort_by, par_sort_slice, sort, sort_by, sort_by_key, sort_slice,
+    };
+    use crate::sort::parallel::ParallelConfig;
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::bool_assertions::BooleanAssert;
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
+    use std::panic;
+    use std::panic::AssertUnwindSafe;
 
     #[test]
     fn basics() {
@@ -88,4 +210,122 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn owned_values() {
+        let array: Vec<String> = vec!["d", "b", "e", "a", "c"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+                "d".to_string(),
+                "e".to_string(),
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn panic_in_compare_keeps_every_element() {
+        let mut array: Vec<String> = (0..20).rev().map(|value| value.to_string()).collect();
+        let mut comparisons = 0;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            sort_by(&mut array, |a, b| {
+                comparisons += 1;
+                if comparisons == 30 {
+                    panic!("compare failed");
+                }
+                a.cmp(b)
+            })
+        }));
+
+        BooleanAssert::assert_that(result.is_err()).is_true();
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(
+                (0..20).map(|value| value.to_string()).collect(),
+            ))
+            .in_any_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
+
+    #[test]
+    fn basics_par_sort() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = par_sort(array, &ParallelConfig::new(4, 1));
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn par_sort_same_as_sequential() {
+        let array: Vec<i32> = (0..5000).map(|value| (value * 7919) % 1009).collect();
+        let mut sequential = array.clone();
+        sort_slice(&mut sequential);
+        let mut parallel = array;
+
+        par_sort_slice(&mut parallel, &ParallelConfig::new(8, 100));
+
+        ListAssert::assert_that(actual_vec(parallel))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(sequential))
+            .in_order();
+    }
+
+    #[test]
+    fn par_sort_stable() {
+        assert_stable(|array| par_sort_by(array, |a, b| a.0.cmp(&b.0), &ParallelConfig::new(3, 8)));
+    }
 }
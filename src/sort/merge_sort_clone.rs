@@ -1,30 +1,62 @@
-use std::fmt::Debug;
+use std::cmp::Ordering;
 
-pub fn sort<T>(array: Vec<T>) -> Vec<T>
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug + Clone,
+    T: Ord + Clone,
 {
-    sort_slice(array.as_slice())
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord + Clone,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Stable: equal elements keep their original order.
+///
+/// Every level of the recursion clones its part of `array` into a new vector.
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let sorted = sorted_copy(array, &mut compare);
+    array.clone_from_slice(&sorted);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
 }
 
-fn sort_slice<T>(array: &[T]) -> Vec<T>
+fn sorted_copy<T, F>(array: &[T], compare: &mut F) -> Vec<T>
 where
-    T: Ord + Debug + Clone,
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
 {
     if array.is_empty() || array.len() == 1 {
         return array.to_vec();
     }
     let middle = array.len() / 2;
     let (first, second) = array.split_at(middle);
-    let sorted_first_part = sort_slice(first);
-    let sorted_second_part = sort_slice(second);
+    let sorted_first_part = sorted_copy(first, compare);
+    let sorted_second_part = sorted_copy(second, compare);
 
-    merge_sorted(sorted_first_part, sorted_second_part)
+    merge_sorted(sorted_first_part, sorted_second_part, compare)
 }
 
-fn merge_sorted<T>(first_array: Vec<T>, second_array: Vec<T>) -> Vec<T>
+fn merge_sorted<T, F>(first_array: Vec<T>, second_array: Vec<T>, compare: &mut F) -> Vec<T>
 where
-    T: Ord + Debug + Clone,
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
 {
     let mut merged = Vec::with_capacity(first_array.len() + second_array.len());
     let mut first_index: usize = 0;
@@ -35,12 +67,13 @@ where
         let second_value = second_array.get(second_index);
         match (first_value, second_value) {
             (Some(first), Some(second)) => {
-                if first.lt(second) {
-                    merged.push(first.to_owned());
-                    first_index += 1;
-                } else {
+                // taking the first part on equality keeps the sort stable
+                if compare(second, first).is_lt() {
                     merged.push(second.to_owned());
                     second_index += 1;
+                } else {
+                    merged.push(first.to_owned());
+                    first_index += 1;
                 }
             }
             (Some(value), None) => {
@@ -60,7 +93,8 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::sort::merge_sort_clone::sort;
+    use crate::sort::merge_sort_clone::{sort, sort_by, sort_by_key, sort_slice};
+    use crate::sort::test_utils::assert_stable;
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
 
@@ -75,4 +109,45 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
 }
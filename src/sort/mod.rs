@@ -1,9 +1,85 @@
+//! Stable sorts keep equal elements in their original order, which matters when sorting by a key:
+//!
+//! | algorithm                     | stable |
+//! |-------------------------------|--------|
+//! | `bottom_up_merge_sort`        | yes    |
+//! | `insertion_sort`              | yes    |
+//! | `merge_sort`                  | yes    |
+//! | `merge_sort_clone`            | yes    |
+//! | `natural_merge_sort`          | yes    |
+//! | `counting_sort`               | yes    |
+//! | `external_sort`               | yes    |
+//! | `lsd_radix_sort`              | yes    |
+//! | `msd_radix_sort`              | yes    |
+//! | `selection_sort`              | no     |
+//! | `shell_sort`                  | no     |
+//! | `quick_sort`                  | no     |
+//! | `three_way_quick_sort`        | no     |
+//! | `three_way_string_quick_sort` | no     |
+//! | `heap_sort`                   | no     |
+//! | `intro_sort`                  | no     |
+
+pub mod benchmark;
+pub mod bottom_up_merge_sort;
+pub mod counting_sort;
+pub mod external_sort;
 pub mod heap_sort;
 pub mod insertion_sort;
+pub mod instrumentation;
+pub mod intro_sort;
+pub mod inversions;
+pub mod lsd_radix_sort;
 pub mod merge_sort;
 pub mod merge_sort_clone;
+pub mod msd_radix_sort;
+pub mod natural_merge_sort;
+pub mod order;
+pub mod parallel;
+pub mod quick_select;
 pub mod quick_sort;
 pub mod selection_sort;
 pub mod shell_sort;
 pub mod shuffle_sort;
 pub mod three_way_quick_sort;
+pub mod three_way_string_quick_sort;
+
+#[cfg(test)]
+mod test_utils {
+    use easy_assert::bool_assertions::BooleanAssert;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Element used to check stability: a key to sort by and the original position
+    pub type Record = (u8, usize);
+
+    /// Sorts random arrays with only a few distinct keys by `sort_by_key` and checks
+    /// that records with equal keys keep their original order
+    pub fn assert_stable(sort_by_key: impl Fn(&mut [Record])) {
+        let mut rng = StdRng::seed_from_u64(42);
+        for len in 0..100 {
+            let mut array: Vec<Record> =
+                (0..len).map(|index| (rng.gen_range(0..5), index)).collect();
+
+            sort_by_key(&mut array);
+
+            BooleanAssert::assert_that(is_stable_sorted(&array, len)).is_true();
+        }
+    }
+
+    fn is_stable_sorted(array: &[Record], len: usize) -> bool {
+        let mut seen = vec![false; len];
+        for (index, (_, original_index)) in array.iter().enumerate() {
+            seen[*original_index] = true;
+            if index == 0 {
+                continue;
+            }
+            let (previous_key, previous_index) = array[index - 1];
+            let (key, original_index) = array[index];
+            if previous_key > key || (previous_key == key && previous_index > original_index) {
+                return false;
+            }
+        }
+
+        array.len() == len && seen.into_iter().all(|value| value)
+    }
+}
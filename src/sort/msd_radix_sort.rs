@@ -0,0 +1,199 @@
+use crate::sort::{counting_sort, insertion_sort};
+
+/// Every byte value plus one more key for "string has ended"
+const RADIX: usize = 257;
+/// Parts of this size are finished by insertion sort, counting is too expensive for them
+const INSERTION_SORT_CUTOFF: usize = 16;
+
+pub fn sort<S>(mut array: Vec<S>) -> Vec<S>
+where
+    S: AsRef<[u8]>,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+/// Stable: sorts strings of any length by the first byte, then every group of strings
+/// with the same first byte by the second one and so on
+pub fn sort_slice<S>(array: &mut [S])
+where
+    S: AsRef<[u8]>,
+{
+    sort_by_key(array, |value| value.as_ref());
+}
+
+/// Same as [`sort_slice`] for elements sorted by the bytes of their `key`
+pub fn sort_by_key<T, F>(array: &mut [T], mut key: F)
+where
+    F: FnMut(&T) -> &[u8],
+{
+    sort_part(array, 0, &mut key);
+}
+
+/// Byte at `index` or `None` when the string is shorter, so shorter strings go first
+pub(crate) fn byte_at(value: &[u8], index: usize) -> Option<u8> {
+    value.get(index).copied()
+}
+
+fn sort_part<T, F>(array: &mut [T], depth: usize, key: &mut F)
+where
+    F: FnMut(&T) -> &[u8],
+{
+    if array.len() <= INSERTION_SORT_CUTOFF {
+        // all strings in the part share the first `depth` bytes
+        insertion_sort::sort_by(array, |first, second| {
+            key(first)[depth..].cmp(&key(second)[depth..])
+        });
+        return;
+    }
+
+    counting_sort::sort_by_key(array, RADIX, |value| key_at(key(value), depth));
+
+    let mut start = 0;
+    while start < array.len() {
+        let byte_key = key_at(key(&array[start]), depth);
+        let mut end = start + 1;
+        while end < array.len() && key_at(key(&array[end]), depth) == byte_key {
+            end += 1;
+        }
+        // strings which have ended are equal, there is nothing left to compare
+        if byte_key > 0 {
+            sort_part(&mut array[start..end], depth + 1, key);
+        }
+        start = end;
+    }
+}
+
+fn key_at(value: &[u8], index: usize) -> usize {
+    byte_at(value, index).map_or(0, |byte| byte as usize + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::msd_radix_sort::{sort, sort_by_key, sort_slice};
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    /// String which remembers its original position
+    struct Tagged(String, usize);
+
+    impl AsRef<[u8]> for Tagged {
+        fn as_ref(&self) -> &[u8] {
+            self.0.as_bytes()
+        }
+    }
+
+    #[test]
+    fn basics() {
+        let array = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "the",
+            "shells",
+            "she",
+            "sells",
+            "are",
+            "surely",
+            "seashells",
+        ];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "are",
+                "by",
+                "sea",
+                "seashells",
+                "seashells",
+                "sells",
+                "sells",
+                "she",
+                "she",
+                "shells",
+                "shore",
+                "surely",
+                "the",
+                "the",
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array: Vec<String> = vec!["z", "bb", "b", "", "ab", "a"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        sort_slice(&mut array[1..5]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(
+                vec!["z", "", "ab", "b", "bb", "a"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ))
+            .in_order();
+    }
+
+    #[test]
+    fn longer_than_cutoff() {
+        let array: Vec<String> = (0..500)
+            .map(|value: u32| ((value * 7919) % 1009).to_string())
+            .collect();
+        let mut expected = array.clone();
+        expected.sort();
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec![(3, "sea"), (1, "by"), (2, "are"), (4, "sea"), (0, "she")];
+
+        sort_by_key(&mut array, |(_, word)| word.as_bytes());
+
+        let positions: Vec<u8> = array.iter().map(|(position, _)| *position).collect();
+        ListAssert::assert_that(actual_vec(positions))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![2, 1, 3, 4, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn stable_by_key() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| std::slice::from_ref(key)));
+    }
+
+    #[test]
+    fn stable() {
+        let mut array: Vec<Tagged> = (0..200)
+            .map(|index: usize| Tagged(((index * 7) % 23).to_string(), index))
+            .collect();
+
+        sort_slice(&mut array);
+
+        let in_order = array.windows(2).all(|pair| {
+            let (first, second) = (&pair[0], &pair[1]);
+            first.0 < second.0 || (first.0 == second.0 && first.1 < second.1)
+        });
+        BooleanAssert::assert_that(in_order).is_true();
+    }
+}
@@ -0,0 +1,473 @@
+use crate::sort::insertion_sort;
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::merge_sort::MergeHole;
+use std::cmp::Ordering;
+use std::{ptr, slice};
+
+/// Arrays shorter than this are a single run extended by insertion sort
+const MIN_MERGE: usize = 32;
+/// Wins in a row after which a merge switches to galloping
+const MIN_GALLOP: usize = 7;
+
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
+where
+    T: Ord,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Stable: equal elements keep their original order
+///
+/// Timsort-style merge sort which makes use of the order already present in the input.
+/// Ascending and strictly descending runs are found and the descending ones are reversed,
+/// short runs are extended by insertion sort. Runs are kept on a stack and merged while
+/// their lengths grow slower than Fibonacci numbers, merges gallop through long stretches
+/// taken from one run. Already sorted or reversed input takes N - 1 comparisons.
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison, swap and move goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    let len = array.len();
+    if len <= 1 {
+        return;
+    }
+    let min_run = min_run_length(len);
+    let mut merger = Merger {
+        runs: Vec::new(),
+        buffer: Vec::new(),
+        min_gallop: MIN_GALLOP,
+    };
+
+    let mut start = 0;
+    while start < len {
+        let mut run_len = make_ascending_run(&mut array[start..], operations);
+        if run_len < min_run {
+            // the run itself is already sorted, so insertion sort only places the added elements
+            run_len = min_run.min(len - start);
+            insertion_sort::sort_with_operations(&mut array[start..start + run_len], operations);
+        }
+        merger.runs.push(Run {
+            start,
+            len: run_len,
+        });
+        merger.collapse(array, operations);
+        start += run_len;
+    }
+    merger.force_collapse(array, operations);
+}
+
+/// Minimal run length, chosen so that the number of runs is a power of two or slightly less
+fn min_run_length(mut len: usize) -> usize {
+    let mut remainder = 0;
+    while len >= MIN_MERGE {
+        remainder |= len & 1;
+        len >>= 1;
+    }
+
+    len + remainder
+}
+
+/// Returns the length of the run at the start of `array`, a strictly descending run is reversed.
+///
+/// Descending runs have to be strict, otherwise reversing them would break stability.
+fn make_ascending_run<T, O>(array: &mut [T], operations: &mut O) -> usize
+where
+    O: SortOperations<T>,
+{
+    if array.len() <= 1 {
+        return array.len();
+    }
+    let mut end = 2;
+    if operations.compare(&array[1], &array[0]).is_lt() {
+        while end < array.len() && operations.compare(&array[end], &array[end - 1]).is_lt() {
+            end += 1;
+        }
+        for i in 0..end / 2 {
+            operations.swap(array, i, end - 1 - i);
+        }
+    } else {
+        while end < array.len() && !operations.compare(&array[end], &array[end - 1]).is_lt() {
+            end += 1;
+        }
+    }
+
+    end
+}
+
+#[derive(Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+struct Merger<T> {
+    runs: Vec<Run>,
+    buffer: Vec<T>,
+    min_gallop: usize,
+}
+
+impl<T> Merger<T> {
+    /// Merges runs on top of the stack until, from the bottom, every run is longer than
+    /// the next two together and longer than the next one
+    fn collapse<O>(&mut self, array: &mut [T], operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            let runs = &self.runs;
+            if (n > 0 && runs[n - 1].len <= runs[n].len + runs[n + 1].len)
+                || (n > 1 && runs[n - 2].len <= runs[n - 1].len + runs[n].len)
+            {
+                if runs[n - 1].len < runs[n + 1].len {
+                    n -= 1;
+                }
+            } else if runs[n].len > runs[n + 1].len {
+                break;
+            }
+            self.merge_at(n, array, operations);
+        }
+    }
+
+    fn force_collapse<O>(&mut self, array: &mut [T], operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            if n > 0 && self.runs[n - 1].len < self.runs[n + 1].len {
+                n -= 1;
+            }
+            self.merge_at(n, array, operations);
+        }
+    }
+
+    /// Merges runs `n` and `n + 1` of the stack
+    fn merge_at<O>(&mut self, n: usize, array: &mut [T], operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        let first = self.runs[n];
+        let second = self.runs.remove(n + 1);
+        self.runs[n].len += second.len;
+        let part = &mut array[first.start..second.start + second.len];
+
+        // elements of the first run not greater than the head of the second one are in place
+        let head = gallop(&part[..first.len], operations, |operations, value| {
+            !operations.compare(&part[first.len], value).is_lt()
+        });
+        if head == first.len {
+            return;
+        }
+        // elements of the second run not less than the tail of the first one are in place
+        let tail = first.len
+            + gallop(&part[first.len..], operations, |operations, value| {
+                operations.compare(value, &part[first.len - 1]).is_lt()
+            });
+
+        self.merge(&mut part[head..tail], first.len - head, operations);
+    }
+
+    /// Same as [`crate::sort::merge_sort`] merge, but once one run wins `min_gallop` times
+    /// in a row, the length of the whole stretch taken from it is found by galloping.
+    fn merge<O>(&mut self, array: &mut [T], middle: usize, operations: &mut O)
+    where
+        O: SortOperations<T>,
+    {
+        self.buffer.reserve(middle);
+        let len = array.len();
+        operations.moved(middle + len);
+        unsafe {
+            let array_ptr = array.as_mut_ptr();
+            ptr::copy_nonoverlapping(array_ptr, self.buffer.as_mut_ptr(), middle);
+
+            let mut hole = MergeHole {
+                buffer: self.buffer.as_ptr(),
+                first_index: 0,
+                first_end: middle,
+                array: array_ptr,
+                destination: 0,
+            };
+            let mut second_index = middle;
+            let mut first_wins = 0;
+            let mut second_wins = 0;
+            while hole.first_index < hole.first_end && second_index < len {
+                if first_wins.max(second_wins) < self.min_gallop {
+                    let first = &*hole.buffer.add(hole.first_index);
+                    let second = &*array_ptr.add(second_index);
+                    if operations.compare(second, first).is_lt() {
+                        ptr::copy_nonoverlapping(second, array_ptr.add(hole.destination), 1);
+                        second_index += 1;
+                        second_wins += 1;
+                        first_wins = 0;
+                    } else {
+                        ptr::copy_nonoverlapping(first, array_ptr.add(hole.destination), 1);
+                        hole.first_index += 1;
+                        first_wins += 1;
+                        second_wins = 0;
+                    }
+                    hole.destination += 1;
+                    continue;
+                }
+
+                let second = &*array_ptr.add(second_index);
+                let first_part = slice::from_raw_parts(
+                    hole.buffer.add(hole.first_index),
+                    hole.first_end - hole.first_index,
+                );
+                let first_count = gallop(first_part, operations, |operations, value| {
+                    !operations.compare(second, value).is_lt()
+                });
+                ptr::copy_nonoverlapping(
+                    first_part.as_ptr(),
+                    array_ptr.add(hole.destination),
+                    first_count,
+                );
+                hole.first_index += first_count;
+                hole.destination += first_count;
+                if hole.first_index == hole.first_end {
+                    break;
+                }
+
+                let first = &*hole.buffer.add(hole.first_index);
+                let second_part =
+                    slice::from_raw_parts(array_ptr.add(second_index), len - second_index);
+                let second_count = gallop(second_part, operations, |operations, value| {
+                    operations.compare(value, first).is_lt()
+                });
+                // the stretch may overlap the place it goes to
+                ptr::copy(
+                    second_part.as_ptr(),
+                    array_ptr.add(hole.destination),
+                    second_count,
+                );
+                second_index += second_count;
+                hole.destination += second_count;
+
+                if first_count < MIN_GALLOP && second_count < MIN_GALLOP {
+                    // galloping does not pay off, leave it and make it harder to enter again
+                    self.min_gallop += 1;
+                    first_wins = 0;
+                    second_wins = 0;
+                } else {
+                    self.min_gallop = self.min_gallop.saturating_sub(1).max(1);
+                }
+            }
+        }
+    }
+}
+
+/// Number of leading elements of `array` matching `is_before`, which has to hold for a prefix
+/// of `array` only. Checks indices 0, 1, 3, 7, ... and then binary searches the last step,
+/// so a stretch of k elements takes about 2 lg k comparisons.
+fn gallop<T, O, P>(array: &[T], operations: &mut O, mut is_before: P) -> usize
+where
+    P: FnMut(&mut O, &T) -> bool,
+{
+    let mut low = 0;
+    let mut step = 1;
+    while low + step <= array.len() && is_before(operations, &array[low + step - 1]) {
+        low += step;
+        step *= 2;
+    }
+    let mut high = array.len().min(low + step - 1);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if is_before(operations, &array[middle]) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::instrumentation::Instrumented;
+    use crate::sort::natural_merge_sort::{
+        sort, sort_by, sort_by_key, sort_slice, sort_with_operations,
+    };
+    use crate::sort::test_utils::assert_stable;
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::panic;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn basics() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_as_std_sort() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for len in [0, 1, 2, 31, 32, 33, 100, 1000, 10_000] {
+            for distinct in [2, 100, 1_000_000] {
+                let mut array: Vec<u32> = (0..len).map(|_| rng.gen_range(0..distinct)).collect();
+                // appended batches: a few sorted runs, some of them reversed
+                for (index, run) in array.chunks_mut(len / 5 + 1).enumerate() {
+                    match index % 3 {
+                        0 => run.sort(),
+                        1 => run.sort_by(|a, b| b.cmp(a)),
+                        _ => {}
+                    }
+                }
+                let mut expected = array.clone();
+                expected.sort();
+
+                sort_slice(&mut array);
+
+                ListAssert::assert_that(actual_vec(array))
+                    .with_element_matcher(|a, b| a.eq(b))
+                    .is_equal_to(expected_vec(expected))
+                    .in_order();
+            }
+        }
+    }
+
+    #[test]
+    fn sorted_input_takes_linear_comparisons() {
+        let len = 10_000;
+        let mut ascending: Vec<u32> = (0..len).collect();
+        let mut descending: Vec<u32> = (0..len).rev().collect();
+
+        let ascending_stats = Instrumented::new(u32::cmp).run(&mut ascending, sort_with_operations);
+        let descending_stats =
+            Instrumented::new(u32::cmp).run(&mut descending, sort_with_operations);
+
+        NumericAssert::assert_that(actual(ascending_stats.comparisons))
+            .is_equal()
+            .to(expected(len as usize - 1));
+        NumericAssert::assert_that(actual(descending_stats.comparisons))
+            .is_equal()
+            .to(expected(len as usize - 1));
+        ListAssert::assert_that(actual_vec(descending))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec((0..len).collect()))
+            .in_order();
+    }
+
+    #[test]
+    fn appended_batches_take_few_comparisons() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut array: Vec<u32> = Vec::new();
+        for _ in 0..4 {
+            let mut batch: Vec<u32> = (0..2500).map(|_| rng.gen_range(0..1_000_000)).collect();
+            batch.sort();
+            array.extend(batch);
+        }
+
+        let stats = Instrumented::new(u32::cmp).run(&mut array, sort_with_operations);
+
+        // finding the runs takes N comparisons and merging them in two levels about 2 N,
+        // while sorting from scratch takes about N lg N = 13 N
+        NumericAssert::assert_that(actual(stats.comparisons))
+            .is_less_or_equal()
+            .to(expected(4 * array.len()));
+    }
+
+    #[test]
+    fn panic_in_compare_keeps_every_element() {
+        let mut array: Vec<String> = (0..200)
+            .map(|value| ((value * 7) % 200).to_string())
+            .collect();
+        let mut comparisons = 0;
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            sort_by(&mut array, |a, b| {
+                comparisons += 1;
+                if comparisons == 1000 {
+                    panic!("compare failed");
+                }
+                a.cmp(b)
+            })
+        }));
+
+        BooleanAssert::assert_that(result.is_err()).is_true();
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(
+                (0..200).map(|value| value.to_string()).collect(),
+            ))
+            .in_any_order();
+    }
+
+    #[test]
+    fn stable() {
+        assert_stable(|array| sort_by_key(array, |(key, _)| *key));
+    }
+}
@@ -0,0 +1,219 @@
+//! Checks whether a slice is sorted and sorting through a permutation of indices
+use crate::sort::counting_sort::move_to_destination;
+use crate::sort::merge_sort;
+use std::cmp::Ordering;
+
+pub fn is_sorted<T>(array: &[T]) -> bool
+where
+    T: Ord,
+{
+    is_sorted_by(array, T::cmp)
+}
+
+/// `true` when no element is less than the one before it according to `compare`
+pub fn is_sorted_by<T, F>(array: &[T], compare: F) -> bool
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    first_inversion_by(array, compare).is_none()
+}
+
+pub fn is_sorted_by_key<T, K, F>(array: &[T], mut key: F) -> bool
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    is_sorted_by(array, |first, second| key(first).cmp(&key(second)))
+}
+
+pub fn first_inversion<T>(array: &[T]) -> Option<usize>
+where
+    T: Ord,
+{
+    first_inversion_by(array, T::cmp)
+}
+
+/// Index of the first element which is greater than the next one, `None` for a sorted slice
+pub fn first_inversion_by<T, F>(array: &[T], mut compare: F) -> Option<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    (1..array.len())
+        .find(|index| compare(&array[*index], &array[index - 1]).is_lt())
+        .map(|index| index - 1)
+}
+
+pub fn argsort<T>(array: &[T]) -> Vec<usize>
+where
+    T: Ord,
+{
+    argsort_by(array, T::cmp)
+}
+
+/// Indices of `array` in sorted order: `array[permutation[0]]` is the least element.
+/// The array itself is not touched.
+///
+/// Stable: indices of equal elements stay in increasing order
+pub fn argsort_by<T, F>(array: &[T], mut compare: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut permutation: Vec<usize> = (0..array.len()).collect();
+    merge_sort::sort_by(&mut permutation, |first, second| {
+        compare(&array[*first], &array[*second])
+    });
+
+    permutation
+}
+
+pub fn argsort_by_key<T, K, F>(array: &[T], mut key: F) -> Vec<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    argsort_by(array, |first, second| key(first).cmp(&key(second)))
+}
+
+/// Rearranges `array` so that its element at `index` is the one which was at `permutation[index]`,
+/// so `apply_permutation(&mut array, &argsort(&array))` sorts the array.
+///
+/// Elements are swapped along permutation cycles, nothing is cloned.
+///
+/// # Panics
+///
+/// If `permutation` is not a permutation of the indices of `array`
+pub fn apply_permutation<T>(array: &mut [T], permutation: &[usize]) {
+    assert_eq!(
+        array.len(),
+        permutation.len(),
+        "permutation has to have an index for every element"
+    );
+    let mut destination = vec![usize::MAX; array.len()];
+    for (index, source) in permutation.iter().enumerate() {
+        assert!(
+            *source < array.len() && destination[*source] == usize::MAX,
+            "index {} is out of range or repeated",
+            source
+        );
+        destination[*source] = index;
+    }
+
+    move_to_destination(array, &mut destination);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::order::{
+        apply_permutation, argsort, argsort_by_key, first_inversion, first_inversion_by, is_sorted,
+        is_sorted_by, is_sorted_by_key,
+    };
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+    use std::panic;
+
+    #[test]
+    fn sorted() {
+        BooleanAssert::assert_that(is_sorted(&[0, 1, 1, 2, 5])).is_true();
+        BooleanAssert::assert_that(is_sorted::<i32>(&[])).is_true();
+        BooleanAssert::assert_that(is_sorted(&[7])).is_true();
+        BooleanAssert::assert_that(is_sorted(&[0, 2, 1])).is_false();
+    }
+
+    #[test]
+    fn sorted_by() {
+        BooleanAssert::assert_that(is_sorted_by(&[3.5, 2.0, 2.0, 0.5], |a, b| {
+            b.partial_cmp(a).unwrap()
+        }))
+        .is_true();
+        BooleanAssert::assert_that(is_sorted_by_key(&["a", "bb", "c"], |value| value.len()))
+            .is_false();
+    }
+
+    #[test]
+    fn basics_first_inversion() {
+        let inversion = first_inversion(&[1, 2, 2, 5, 3, 4, 0]).unwrap_or(usize::MAX);
+        let inversion_by = first_inversion_by(&[3, 2, 4], |a, b| b.cmp(a)).unwrap_or(usize::MAX);
+
+        NumericAssert::assert_that(actual(inversion))
+            .is_equal()
+            .to(expected(3));
+        NumericAssert::assert_that(actual(inversion_by))
+            .is_equal()
+            .to(expected(1));
+        OptionAssert::assert_that(first_inversion(&[1, 2, 2, 5])).is_none();
+    }
+
+    #[test]
+    fn basics_argsort() {
+        let array = vec!["d", "b", "e", "a", "c"];
+
+        let permutation = argsort(&array);
+
+        ListAssert::assert_that(actual_vec(permutation))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![3, 1, 4, 0, 2]))
+            .in_order();
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["d", "b", "e", "a", "c"]))
+            .in_order();
+    }
+
+    #[test]
+    fn argsort_stable() {
+        let array = vec!["ccc", "a", "dd", "b", "ee", "f"];
+
+        let permutation = argsort_by_key(&array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(permutation))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![1, 3, 5, 2, 4, 0]))
+            .in_order();
+    }
+
+    #[test]
+    fn apply_argsort_sorts() {
+        let mut array: Vec<String> = (0..1000).map(|value| (value % 97).to_string()).collect();
+        array.shuffle(&mut StdRng::seed_from_u64(42));
+        let mut expected_array = array.clone();
+        expected_array.sort();
+
+        let permutation = argsort(&array);
+        apply_permutation(&mut array, &permutation);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected_array))
+            .in_order();
+    }
+
+    #[test]
+    fn apply_permutation_to_other_slice() {
+        let keys = vec![30, 10, 20];
+        let mut names = vec!["thirty", "ten", "twenty"];
+
+        apply_permutation(&mut names, &argsort(&keys));
+
+        ListAssert::assert_that(actual_vec(names))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["ten", "twenty", "thirty"]))
+            .in_order();
+    }
+
+    #[test]
+    fn apply_invalid_permutation() {
+        let repeated = panic::catch_unwind(|| apply_permutation(&mut [1, 2, 3], &[0, 0, 1]));
+        let out_of_range = panic::catch_unwind(|| apply_permutation(&mut [1, 2], &[0, 2]));
+        let too_short = panic::catch_unwind(|| apply_permutation(&mut [1, 2], &[0]));
+
+        BooleanAssert::assert_that(repeated.is_err()).is_true();
+        BooleanAssert::assert_that(out_of_range.is_err()).is_true();
+        BooleanAssert::assert_that(too_short.is_err()).is_true();
+    }
+}
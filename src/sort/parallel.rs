@@ -0,0 +1,30 @@
+use std::thread;
+
+/// Parts shorter than this are sorted sequentially by default
+const DEFAULT_SEQUENTIAL_CUTOFF: usize = 8192;
+
+/// How parallel sorts split their work
+pub struct ParallelConfig {
+    pub(crate) threads: usize,
+    pub(crate) sequential_cutoff: usize,
+}
+
+impl ParallelConfig {
+    /// `threads` is the maximum number of threads working at the same time, parts with
+    /// at most `sequential_cutoff` elements are not split further
+    pub fn new(threads: usize, sequential_cutoff: usize) -> ParallelConfig {
+        ParallelConfig {
+            threads: threads.max(1),
+            sequential_cutoff,
+        }
+    }
+}
+
+impl Default for ParallelConfig {
+    /// One thread per available core
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+        Self::new(threads, DEFAULT_SEQUENTIAL_CUTOFF)
+    }
+}
@@ -0,0 +1,228 @@
+use crate::sort::intro_sort::choose_pivot;
+use crate::sort::three_way_quick_sort::partition;
+use crate::sort::{insertion_sort, intro_sort};
+use std::cmp::Ordering;
+
+/// Parts of this size are finished by insertion sort
+const INSERTION_SORT_CUTOFF: usize = 16;
+/// Size of the groups used to find a median of medians
+const GROUP_SIZE: usize = 5;
+
+/// Returns the `k`-th smallest element (counting from 0) or `None` if `k` is out of range.
+///
+/// Rearranges the slice so the element is at index `k`, everything before it is less or equal
+/// and everything after it is greater or equal.
+pub fn select<T>(array: &mut [T], k: usize) -> Option<&T>
+where
+    T: Ord,
+{
+    select_by(array, k, T::cmp)
+}
+
+/// Same as [`select`], ordered by `compare`.
+///
+/// Expected linear time. If partitioning keeps going badly for `2 * lg N` rounds,
+/// pivots are chosen by median of medians, so the worst case is linear too.
+pub fn select_by<T, F>(array: &mut [T], k: usize, mut compare: F) -> Option<&T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if k >= array.len() {
+        return None;
+    }
+    let rounds_limit = 2 * (usize::BITS - array.len().leading_zeros()) as usize;
+    select_part(array, k, rounds_limit, &mut compare);
+
+    Some(&array[k])
+}
+
+/// Returns the lower median, `None` for an empty slice
+pub fn median<T>(array: &mut [T]) -> Option<&T>
+where
+    T: Ord,
+{
+    median_by(array, T::cmp)
+}
+
+pub fn median_by<T, F>(array: &mut [T], compare: F) -> Option<&T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if array.is_empty() {
+        return None;
+    }
+    let k = (array.len() - 1) / 2;
+
+    select_by(array, k, compare)
+}
+
+/// Moves the `k` smallest elements to the front in sorted order, the rest is left in any order
+pub fn partial_sort<T>(array: &mut [T], k: usize)
+where
+    T: Ord,
+{
+    partial_sort_by(array, k, T::cmp);
+}
+
+pub fn partial_sort_by<T, F>(array: &mut [T], k: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let k = k.min(array.len());
+    if k == 0 {
+        return;
+    }
+    select_by(array, k - 1, &mut compare);
+
+    intro_sort::sort_by(&mut array[..k - 1], compare);
+}
+
+fn select_part<T, F>(array: &mut [T], k: usize, mut rounds_left: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut low = 0;
+    let mut high = array.len();
+    loop {
+        let part = &mut array[low..high];
+        if part.len() <= INSERTION_SORT_CUTOFF {
+            insertion_sort::sort_by(part, compare);
+            return;
+        }
+
+        let pivot_index = if rounds_left == 0 {
+            median_of_medians(part, compare)
+        } else {
+            rounds_left -= 1;
+            choose_pivot(part, compare)
+        };
+        part.swap(0, pivot_index);
+        let (lower_at_place, greater_at_place) = partition(part, compare);
+        if k < low + lower_at_place {
+            high = low + lower_at_place;
+        } else if k > low + greater_at_place {
+            low += greater_at_place + 1;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Returns the index of a median of medians of groups of five, which is guaranteed
+/// to have at least 30% of the elements on each side
+fn median_of_medians<T, F>(array: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let groups = array.len() / GROUP_SIZE;
+    for group in 0..groups {
+        let start = group * GROUP_SIZE;
+        insertion_sort::sort_by(&mut array[start..start + GROUP_SIZE], &mut *compare);
+        // medians are collected at the front of the slice
+        array.swap(group, start + GROUP_SIZE / 2);
+    }
+    let middle = groups / 2;
+    let rounds_limit = 2 * (usize::BITS - groups.leading_zeros()) as usize;
+    select_part(&mut array[..groups], middle, rounds_limit, compare);
+
+    middle
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::quick_select::{median, partial_sort, select, select_by, select_part};
+    use easy_assert::bool_assertions::BooleanAssert;
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::num_assertions::NumericAssert;
+    use easy_assert::option_assertions::OptionAssert;
+    use easy_assert::{actual, actual_vec, expected, expected_vec};
+
+    #[test]
+    fn basics_select() {
+        let mut array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let selected = select(&mut array, 5).copied().unwrap_or(-1);
+
+        NumericAssert::assert_that(actual(selected))
+            .is_equal()
+            .to(expected(4));
+    }
+
+    #[test]
+    fn select_out_of_range() {
+        let mut array = vec![3, 2, 1];
+
+        OptionAssert::assert_that(select(&mut array, 3).copied()).is_none();
+    }
+
+    #[test]
+    fn basics_select_by() {
+        let mut array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let selected = select_by(&mut array, 0, |a, b| b.cmp(a))
+            .copied()
+            .unwrap_or(-1);
+
+        NumericAssert::assert_that(actual(selected))
+            .is_equal()
+            .to(expected(9));
+    }
+
+    #[test]
+    fn basics_median() {
+        let mut odd = vec![7, 1, 5, 3, 9];
+        let mut even = vec![8, 2, 6, 4];
+
+        NumericAssert::assert_that(actual(median(&mut odd).copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(5));
+        NumericAssert::assert_that(actual(median(&mut even).copied().unwrap_or(-1)))
+            .is_equal()
+            .to(expected(4));
+        OptionAssert::assert_that(median::<i32>(&mut []).copied()).is_none();
+    }
+
+    #[test]
+    fn basics_partial_sort() {
+        let mut array: Vec<i32> = (0..100).map(|value| (value * 37) % 101).collect();
+
+        partial_sort(&mut array, 5);
+
+        ListAssert::assert_that(actual_vec(array[..5].to_vec()))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 4]))
+            .in_order();
+    }
+
+    #[test]
+    fn select_every_rank() {
+        let array: Vec<i32> = (0..300).map(|value| (value * 7919) % 61).collect();
+        let mut sorted = array.clone();
+        sorted.sort();
+
+        for k in 0..array.len() {
+            let mut copy = array.clone();
+            let selected = select(&mut copy, k).copied();
+
+            BooleanAssert::assert_that(selected == Some(sorted[k])).is_true();
+            BooleanAssert::assert_that(copy[..k].iter().all(|value| *value <= sorted[k])).is_true();
+            BooleanAssert::assert_that(copy[k..].iter().all(|value| *value >= sorted[k])).is_true();
+        }
+    }
+
+    #[test]
+    fn median_of_medians_fallback() {
+        let array: Vec<i32> = (0..500).map(|value| (value * 7919) % 499).collect();
+        let mut sorted = array.clone();
+        sorted.sort();
+
+        for k in [0, 1, 100, 250, 498, 499] {
+            let mut copy = array.clone();
+            select_part(&mut copy, k, 0, &mut i32::cmp);
+
+            NumericAssert::assert_that(actual(copy[k]))
+                .is_equal()
+                .to(expected(sorted[k]));
+        }
+    }
+}
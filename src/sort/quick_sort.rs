@@ -1,67 +1,135 @@
-use crate::sort::shuffle_sort::shuffle;
-use std::fmt::Debug;
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::shuffle_sort::shuffle_with;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
 
-pub fn sort<T>(array: Vec<T>) -> Vec<T>
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    let array = shuffle(array);
+    sort_slice(&mut array);
 
-    let len = array.len();
+    array
+}
 
-    sort_part(array, 0, len - 1)
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
 }
 
-fn sort_part<T>(array: Vec<T>, low: usize, hi_included: usize) -> Vec<T>
+/// Not stable: partitioning moves elements past equal ones
+pub fn sort_by<T, F>(array: &mut [T], compare: F)
 where
-    T: Ord + Debug,
+    F: FnMut(&T, &T) -> Ordering,
 {
-    if hi_included <= low {
-        return array;
-    }
+    sort_by_with(array, compare, &mut rand::thread_rng());
+}
+
+/// Same as [`sort_by`], but the initial shuffle uses `rng`, so a run can be replayed exactly
+pub fn sort_by_with<T, F, R>(array: &mut [T], mut compare: F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng + ?Sized,
+{
+    sort_with_operations(array, &mut compare, rng);
+}
 
-    let (mut array, elem_at_place) = partition(array, low, hi_included);
-    if elem_at_place > 0 {
-        array = sort_part(array, low, elem_at_place - 1);
+/// Sorts with the initial shuffle seeded by `seed`
+pub fn sort_seeded<T>(array: &mut [T], seed: u64)
+where
+    T: Ord,
+{
+    sort_by_with(array, T::cmp, &mut StdRng::seed_from_u64(seed));
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by_with`], every comparison and swap after the shuffle goes through `operations`
+pub fn sort_with_operations<T, O, R>(array: &mut [T], operations: &mut O, rng: &mut R)
+where
+    O: SortOperations<T>,
+    R: Rng + ?Sized,
+{
+    shuffle_with(array, rng);
+
+    sort_part(array, operations);
+}
+
+fn sort_part<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    if array.len() <= 1 {
+        return;
     }
 
-    sort_part(array, elem_at_place + 1, hi_included)
+    let elem_at_place = partition(array, operations);
+    let (lower, greater) = array.split_at_mut(elem_at_place);
+    sort_part(lower, operations);
+    sort_part(&mut greater[1..], operations);
 }
-fn partition<T>(mut array: Vec<T>, from: usize, till_included: usize) -> (Vec<T>, usize)
+
+/// Partitions around the first element and returns its final index.
+///
+/// Both scans stop on elements equal to the pivot, so arrays with many duplicates
+/// are still split in the middle.
+pub(crate) fn partition<T, O>(array: &mut [T], operations: &mut O) -> usize
 where
-    T: Ord + Debug,
+    O: SortOperations<T>,
 {
-    let mut lower_index: usize = from + 1;
-    let mut greater_index: usize = till_included;
+    let from: usize = 0;
+    let till_included: usize = array.len() - 1;
+    let mut lower_index: usize = from;
+    let mut greater_index: usize = till_included + 1;
     loop {
-        while array[lower_index].le(&array[from]) {
-            lower_index += 1;
-            if lower_index >= till_included {
+        lower_index += 1;
+        while operations
+            .compare(&array[lower_index], &array[from])
+            .is_lt()
+        {
+            if lower_index == till_included {
                 break;
             }
+            lower_index += 1;
         }
 
-        while array[greater_index].gt(&array[from]) {
+        greater_index -= 1;
+        // the pivot itself stops this scan
+        while operations
+            .compare(&array[from], &array[greater_index])
+            .is_lt()
+        {
             greater_index -= 1;
-            if greater_index <= from {
-                break;
-            }
         }
-        if lower_index > greater_index {
+        if lower_index >= greater_index {
             break;
         }
-        array.swap(lower_index, greater_index);
+        operations.swap(array, lower_index, greater_index);
     }
-    array.swap(from, greater_index);
+    operations.swap(array, from, greater_index);
 
-    (array, greater_index)
+    greater_index
 }
 
 #[cfg(test)]
 mod test {
-    use crate::sort::quick_sort::sort;
+    use crate::sort::quick_sort::{
+        sort, sort_by, sort_by_key, sort_by_with, sort_seeded, sort_slice,
+    };
+    use easy_assert::bool_assertions::BooleanAssert;
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn basics() {
@@ -74,4 +142,88 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn empty() {
+        let sorted: Vec<i32> = sort(Vec::new());
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(Vec::new()))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_seeded() {
+        let mut array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        sort_seeded(&mut array, 42);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_seed_same_comparisons() {
+        let array: Vec<i32> = (0..200).map(|value| (value * 31) % 17).collect();
+        let mut first_comparisons = Vec::new();
+        let mut second_comparisons = Vec::new();
+
+        sort_by_with(
+            &mut array.clone(),
+            |a, b| {
+                first_comparisons.push((*a, *b));
+                a.cmp(b)
+            },
+            &mut StdRng::seed_from_u64(5),
+        );
+        sort_by_with(
+            &mut array.clone(),
+            |a, b| {
+                second_comparisons.push((*a, *b));
+                a.cmp(b)
+            },
+            &mut StdRng::seed_from_u64(5),
+        );
+
+        BooleanAssert::assert_that(first_comparisons == second_comparisons).is_true();
+    }
 }
@@ -1,23 +1,59 @@
+use crate::sort::instrumentation::SortOperations;
+use std::cmp::Ordering;
+
 pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
     T: Ord,
 {
-    for index in 0..array.len() {
-        let min_index = find_smallest_from(&mut array, index);
-        array.swap(index, min_index);
-    }
+    sort_slice(&mut array);
 
     array
 }
 
-fn find_smallest_from<T>(array: &mut [T], index: usize) -> usize
+pub fn sort_slice<T>(array: &mut [T])
 where
     T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Not stable: swapping the minimum forward can jump over equal elements
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison and swap goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    for index in 0..array.len() {
+        let min_index = find_smallest_from(array, index, operations);
+        if min_index != index {
+            operations.swap(array, index, min_index);
+        }
+    }
+}
+
+fn find_smallest_from<T, O>(array: &mut [T], index: usize, operations: &mut O) -> usize
+where
+    O: SortOperations<T>,
 {
     let mut min_index = index;
     let start = index + 1;
     for (current_index, current_val) in array.iter().enumerate().skip(start) {
-        if current_val.lt(&array[min_index]) {
+        if operations.compare(current_val, &array[min_index]).is_lt() {
             min_index = current_index;
         }
     }
@@ -26,7 +62,7 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::sort::selection_sort::sort;
+    use crate::sort::selection_sort::{sort, sort_by, sort_by_key, sort_slice};
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
 
@@ -41,4 +77,40 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
 }
@@ -1,44 +1,197 @@
-use std::fmt::Debug;
+use crate::sort::instrumentation::SortOperations;
+use std::cmp::Ordering;
 
 pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    let mut step = compute_step(array.len());
-    while step >= 1 {
+    sort_slice(&mut array);
+
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Not stable: elements jump over equal ones while sorting with a long step
+pub fn sort_by<T, F>(array: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_with_operations(array, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by`], every comparison and swap goes through `operations`
+pub fn sort_with_operations<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    sort_gaps_with_operations(array, &GapSequence::Knuth, operations);
+}
+
+/// Same as [`sort_slice`], but h-sorts with the gaps of `gaps` instead of Knuth's
+pub fn sort_with_gaps<T>(array: &mut [T], gaps: &GapSequence)
+where
+    T: Ord,
+{
+    sort_by_with_gaps(array, gaps, T::cmp);
+}
+
+/// Same as [`sort_by`], but h-sorts with the gaps of `gaps` instead of Knuth's
+pub fn sort_by_with_gaps<T, F>(array: &mut [T], gaps: &GapSequence, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_gaps_with_operations(array, gaps, &mut compare);
+}
+
+/// Same as [`sort_by_with_gaps`], every comparison and swap goes through `operations`
+pub fn sort_gaps_with_operations<T, O>(array: &mut [T], gaps: &GapSequence, operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    for step in gaps.gaps_for(array.len()) {
         for i in step..array.len() {
             let mut j = i;
             while j >= step {
                 let current = &array[j];
                 let prev = &array[j - step];
-                if current.lt(prev) {
-                    array.swap(j, j - step);
+                if operations.compare(current, prev).is_lt() {
+                    operations.swap(array, j, j - step);
                     j -= step;
                 } else {
                     break;
                 }
             }
         }
-        step /= 3;
     }
+}
 
-    array
+/// Gaps used to h-sort the array, from the largest one down to 1
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum GapSequence {
+    /// N/2, N/4, ..., 1
+    Shell,
+    /// 1, 3, 7, 15, ..., 2^k - 1
+    Hibbard,
+    /// 1, 4, 13, 40, ..., (3^k - 1)/2, the default one
+    #[default]
+    Knuth,
+    /// 1, 8, 23, 77, 281, ..., 4^k + 3 * 2^(k-1) + 1
+    Sedgewick,
+    /// 1, 4, 9, 20, 46, 103, ..., ceil(h), where h = 2.25 * h + 1
+    Tokuda,
+    /// 1, 4, 10, 23, 57, 132, 301, 701, each further gap is 2.25 times the previous one
+    Ciura,
+    /// Any gaps in any order, a final pass with gap 1 is always done
+    Custom(Vec<usize>),
 }
 
-fn compute_step(array_size: usize) -> usize {
-    let mut step = 1;
-    while step < array_size / 3 {
-        step = 3 * step + 1;
+const CIURA_GAPS: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
+
+impl GapSequence {
+    /// Gaps to use for an array of `len` elements, in decreasing order and ending with 1
+    pub fn gaps_for(&self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            GapSequence::Shell => {
+                let mut gaps = Vec::new();
+                let mut gap = len / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+                gaps.reverse();
+                gaps
+            }
+            GapSequence::Hibbard => increasing_while(len, |gap, _| 2 * gap + 1),
+            GapSequence::Knuth => increasing_while(len, |gap, _| 3 * gap + 1),
+            GapSequence::Sedgewick => increasing_while(len, |_, k| {
+                4usize.pow(k as u32) + 3 * 2usize.pow(k as u32 - 1) + 1
+            }),
+            GapSequence::Tokuda => {
+                let mut gaps = Vec::new();
+                let mut exact = 1.0f64;
+                while (exact.ceil() as usize) < len {
+                    gaps.push(exact.ceil() as usize);
+                    exact = 2.25 * exact + 1.0;
+                }
+                gaps
+            }
+            GapSequence::Ciura => {
+                let mut gaps: Vec<usize> = CIURA_GAPS
+                    .iter()
+                    .copied()
+                    .take_while(|gap| *gap < len)
+                    .collect();
+                if gaps.len() == CIURA_GAPS.len() {
+                    let mut gap = CIURA_GAPS[CIURA_GAPS.len() - 1];
+                    loop {
+                        gap = gap * 9 / 4;
+                        if gap >= len {
+                            break;
+                        }
+                        gaps.push(gap);
+                    }
+                }
+                gaps
+            }
+            GapSequence::Custom(gaps) => {
+                let mut gaps: Vec<usize> = gaps
+                    .iter()
+                    .copied()
+                    .filter(|gap| *gap > 1 && *gap < len)
+                    .collect();
+                gaps.sort_unstable();
+                gaps.dedup();
+                gaps.insert(0, 1);
+                gaps
+            }
+        };
+        if gaps.is_empty() {
+            gaps.push(1);
+        }
+        gaps.reverse();
+
+        gaps
+    }
+}
+
+/// Gaps from 1 up to `len` (exclusive), `next` gets the last gap and the index of the gap to compute
+fn increasing_while<F>(len: usize, next: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> usize,
+{
+    let mut gaps = Vec::new();
+    let mut gap = 1;
+    while gap < len {
+        gaps.push(gap);
+        gap = next(gap, gaps.len());
     }
 
-    step
+    gaps
 }
 
 #[cfg(test)]
 mod test {
-    use crate::sort::shell_sort::sort;
+    use crate::sort::shell_sort::{
+        sort, sort_by, sort_by_key, sort_by_with_gaps, sort_slice, sort_with_gaps, GapSequence,
+    };
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn basics() {
@@ -51,4 +204,116 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    fn all_sequences() -> Vec<GapSequence> {
+        vec![
+            GapSequence::Shell,
+            GapSequence::Hibbard,
+            GapSequence::Knuth,
+            GapSequence::Sedgewick,
+            GapSequence::Tokuda,
+            GapSequence::Ciura,
+            GapSequence::Custom(vec![5, 3]),
+        ]
+    }
+
+    #[test]
+    fn every_sequence_sorts() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for gaps in all_sequences() {
+            for len in [0, 1, 2, 10, 100, 1000, 5000] {
+                let mut array: Vec<u16> = (0..len).map(|_| rng.gen_range(0..500)).collect();
+                let mut expected = array.clone();
+                expected.sort();
+
+                sort_with_gaps(&mut array, &gaps);
+
+                ListAssert::assert_that(actual_vec(array))
+                    .with_element_matcher(|a, b| a.eq(b))
+                    .is_equal_to(expected_vec(expected))
+                    .in_order();
+            }
+        }
+    }
+
+    #[test]
+    fn every_sequence_sorts_by() {
+        for gaps in all_sequences() {
+            let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+            sort_by_with_gaps(&mut array, &gaps, |a, b| b.partial_cmp(a).unwrap());
+
+            ListAssert::assert_that(actual_vec(array))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn gaps() {
+        let cases = [
+            (GapSequence::Shell, vec![50, 25, 12, 6, 3, 1]),
+            (GapSequence::Hibbard, vec![63, 31, 15, 7, 3, 1]),
+            (GapSequence::Knuth, vec![40, 13, 4, 1]),
+            (GapSequence::Sedgewick, vec![77, 23, 8, 1]),
+            (GapSequence::Tokuda, vec![46, 20, 9, 4, 1]),
+            (GapSequence::Ciura, vec![57, 23, 10, 4, 1]),
+            (GapSequence::Custom(vec![7, 0, 300, 7, 2]), vec![7, 2, 1]),
+        ];
+        for (gaps, expected) in cases {
+            ListAssert::assert_that(actual_vec(gaps.gaps_for(100)))
+                .with_element_matcher(|a, b| a.eq(b))
+                .is_equal_to(expected_vec(expected))
+                .in_order();
+        }
+    }
+
+    #[test]
+    fn ciura_gaps_are_extended() {
+        let gaps = GapSequence::Ciura.gaps_for(5000);
+
+        ListAssert::assert_that(actual_vec(gaps))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                3548, 1577, 701, 301, 132, 57, 23, 10, 4, 1,
+            ]))
+            .in_order();
+    }
 }
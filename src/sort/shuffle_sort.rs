@@ -1,25 +1,40 @@
-use rand::Rng;
-use std::fmt::Debug;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-pub fn shuffle<T>(mut array: Vec<T>) -> Vec<T>
+pub fn shuffle<T>(mut array: Vec<T>) -> Vec<T> {
+    shuffle_slice(&mut array);
+
+    array
+}
+
+pub fn shuffle_slice<T>(array: &mut [T]) {
+    shuffle_with(array, &mut rand::thread_rng());
+}
+
+/// Shuffles with the given generator, so the same generator state always gives the same order
+pub fn shuffle_with<T, R>(array: &mut [T], rng: &mut R)
 where
-    T: Ord + Debug,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     for index in 0..array.len() {
         let rand_to = index + 1;
         let rand = rng.gen_range(0..rand_to);
         array.swap(index, rand);
     }
+}
 
-    array
+/// Shuffles with a generator created from `seed`, same seed gives the same order
+pub fn shuffle_seeded<T>(array: &mut [T], seed: u64) {
+    shuffle_with(array, &mut StdRng::seed_from_u64(seed));
 }
 
 #[cfg(test)]
 mod test {
-    use crate::sort::shuffle_sort::shuffle;
+    use crate::sort::shuffle_sort::{shuffle, shuffle_seeded, shuffle_with};
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn basics() {
@@ -44,4 +59,32 @@ mod test {
             .is_not_equal_to(expected_vec(vec![3, 2, 3, 4, 1, 6, 9, 0]))
             .in_order();
     }
+
+    #[test]
+    fn same_seed_same_order() {
+        let mut first: Vec<i32> = (0..50).collect();
+        let mut second: Vec<i32> = (0..50).collect();
+
+        shuffle_seeded(&mut first, 7);
+        shuffle_seeded(&mut second, 7);
+
+        ListAssert::assert_that(actual_vec(first))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(second))
+            .in_order();
+    }
+
+    #[test]
+    fn same_rng_state_same_order() {
+        let mut first: Vec<i32> = (0..50).collect();
+        let mut second: Vec<i32> = (0..50).collect();
+
+        shuffle_with(&mut first, &mut StdRng::seed_from_u64(11));
+        shuffle_with(&mut second, &mut StdRng::seed_from_u64(11));
+
+        ListAssert::assert_that(actual_vec(first))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(second))
+            .in_order();
+    }
 }
@@ -1,51 +1,163 @@
-use crate::sort::shuffle_sort::shuffle;
+use crate::sort::instrumentation::SortOperations;
+use crate::sort::parallel::ParallelConfig;
+use crate::sort::shuffle_sort::shuffle_with;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::cmp::Ordering;
 use std::cmp::Ordering::Less;
-use std::fmt::Debug;
+use std::thread;
 use Ordering::{Equal, Greater};
 
-pub fn sort<T>(array: Vec<T>) -> Vec<T>
+pub fn sort<T>(mut array: Vec<T>) -> Vec<T>
 where
-    T: Ord + Debug,
+    T: Ord,
 {
-    let array = shuffle(array);
+    sort_slice(&mut array);
 
-    let len = array.len();
+    array
+}
+
+pub fn sort_slice<T>(array: &mut [T])
+where
+    T: Ord,
+{
+    sort_by(array, T::cmp);
+}
+
+/// Not stable: partitioning moves elements past equal ones
+pub fn sort_by<T, F>(array: &mut [T], compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_by_with(array, compare, &mut rand::thread_rng());
+}
+
+/// Same as [`sort_by`], but the initial shuffle uses `rng`, so a run can be replayed exactly
+pub fn sort_by_with<T, F, R>(array: &mut [T], mut compare: F, rng: &mut R)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng + ?Sized,
+{
+    sort_with_operations(array, &mut compare, rng);
+}
+
+/// Sorts with the initial shuffle seeded by `seed`
+pub fn sort_seeded<T>(array: &mut [T], seed: u64)
+where
+    T: Ord,
+{
+    sort_by_with(array, T::cmp, &mut StdRng::seed_from_u64(seed));
+}
+
+pub fn sort_by_key<T, K, F>(array: &mut [T], mut key: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(array, |first, second| key(first).cmp(&key(second)));
+}
+
+/// Same as [`sort_by_with`], every comparison and swap after the shuffle goes through `operations`
+pub fn sort_with_operations<T, O, R>(array: &mut [T], operations: &mut O, rng: &mut R)
+where
+    O: SortOperations<T>,
+    R: Rng + ?Sized,
+{
+    shuffle_with(array, rng);
+
+    sort_part(array, operations);
+}
 
-    sort_part(array, 0, len - 1)
+pub fn par_sort<T>(mut array: Vec<T>, config: &ParallelConfig) -> Vec<T>
+where
+    T: Ord + Send,
+{
+    par_sort_slice(&mut array, config);
+
+    array
+}
+
+pub fn par_sort_slice<T>(array: &mut [T], config: &ParallelConfig)
+where
+    T: Ord + Send,
+{
+    par_sort_by(array, T::cmp, config);
+}
+
+pub fn par_sort_by<T, F>(array: &mut [T], compare: F, config: &ParallelConfig)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    par_sort_by_with(array, compare, &mut rand::thread_rng(), config);
+}
+
+/// Same result as [`sort_by_with`] with the same `rng`: parts left and right of the pivot
+/// are sorted on different threads until they get shorter than the sequential cutoff
+/// or threads run out
+pub fn par_sort_by_with<T, F, R>(array: &mut [T], compare: F, rng: &mut R, config: &ParallelConfig)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+    R: Rng + ?Sized,
+{
+    shuffle_with(array, rng);
+
+    par_sort_part(array, &compare, config.threads, config.sequential_cutoff);
 }
 
-fn sort_part<T>(array: Vec<T>, low: usize, hi_included: usize) -> Vec<T>
+fn par_sort_part<T, F>(array: &mut [T], compare: &F, threads: usize, sequential_cutoff: usize)
 where
-    T: Ord + Debug,
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
 {
-    if hi_included <= low {
-        return array;
+    let mut compare_ref = compare;
+    if threads <= 1 || array.len() <= sequential_cutoff.max(1) {
+        sort_part(array, &mut compare_ref);
+        return;
     }
 
-    let (mut array, lower_at_place, greater_at_place) = partition(array, low, hi_included);
-    if lower_at_place > 0 {
-        array = sort_part(array, low, lower_at_place - 1);
+    let (lower_at_place, greater_at_place) = partition(array, &mut compare_ref);
+    let (lower_and_equal, greater) = array.split_at_mut(greater_at_place + 1);
+    let lower = &mut lower_and_equal[..lower_at_place];
+    thread::scope(|scope| {
+        scope.spawn(|| par_sort_part(lower, compare, threads / 2, sequential_cutoff));
+        par_sort_part(greater, compare, threads - threads / 2, sequential_cutoff);
+    });
+}
+
+fn sort_part<T, O>(array: &mut [T], operations: &mut O)
+where
+    O: SortOperations<T>,
+{
+    if array.len() <= 1 {
+        return;
     }
 
-    sort_part(array, greater_at_place + 1, hi_included)
+    let (lower_at_place, greater_at_place) = partition(array, operations);
+    let (lower_and_equal, greater) = array.split_at_mut(greater_at_place + 1);
+    sort_part(&mut lower_and_equal[..lower_at_place], operations);
+    sort_part(greater, operations);
 }
-fn partition<T>(mut array: Vec<T>, from: usize, till_included: usize) -> (Vec<T>, usize, usize)
+
+/// Partitions around the first element. Returns the range `lower..=greater` of elements
+/// equal to it: everything before is less and everything after is greater.
+pub(crate) fn partition<T, O>(array: &mut [T], operations: &mut O) -> (usize, usize)
 where
-    T: Ord + Debug,
+    O: SortOperations<T>,
 {
-    let mut lower_index: usize = from;
-    let mut greater_index: usize = till_included;
+    let mut lower_index: usize = 0;
+    let mut greater_index: usize = array.len() - 1;
     let mut current_index: usize = lower_index;
     loop {
-        match array[current_index].cmp(&array[lower_index]) {
+        match operations.compare(&array[current_index], &array[lower_index]) {
             Less => {
-                array.swap(current_index, lower_index);
+                operations.swap(array, current_index, lower_index);
                 lower_index += 1;
                 current_index += 1;
             }
             Greater => {
-                array.swap(current_index, greater_index);
+                operations.swap(array, current_index, greater_index);
                 greater_index -= 1;
             }
             Equal => {
@@ -57,14 +169,21 @@ where
         }
     }
 
-    (array, lower_index, greater_index)
+    (lower_index, greater_index)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::sort::three_way_quick_sort::sort;
+    use crate::sort::parallel::ParallelConfig;
+    use crate::sort::three_way_quick_sort::{
+        par_sort, par_sort_by_with, sort, sort_by, sort_by_key, sort_by_with, sort_seeded,
+        sort_slice,
+    };
+    use easy_assert::bool_assertions::BooleanAssert;
     use easy_assert::list_assertions::ListAssert;
     use easy_assert::{actual_vec, expected_vec};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn basics() {
@@ -77,4 +196,121 @@ mod test {
             .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
             .in_order();
     }
+
+    #[test]
+    fn basics_slice() {
+        let mut array = vec![7, 3, 2, 3, 4, 1, 6, 9, 0, -1];
+
+        sort_slice(&mut array[1..9]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![7, 0, 1, 2, 3, 3, 4, 6, 9, -1]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by() {
+        let mut array = vec![3.5, 2.0, 3.0, 4.25, 1.0, 6.0, 9.5, 0.0];
+
+        sort_by(&mut array, |a, b| b.partial_cmp(a).unwrap());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![9.5, 6.0, 4.25, 3.5, 3.0, 2.0, 1.0, 0.0]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_by_key() {
+        let mut array = vec!["ccc", "a", "dddd", "bb"];
+
+        sort_by_key(&mut array, |value| value.len());
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec!["a", "bb", "ccc", "dddd"]))
+            .in_order();
+    }
+
+    #[test]
+    fn empty() {
+        let sorted: Vec<i32> = sort(Vec::new());
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(Vec::new()))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_seeded() {
+        let mut array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        sort_seeded(&mut array, 42);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn same_seed_same_comparisons() {
+        let array: Vec<i32> = (0..200).map(|value| (value * 31) % 17).collect();
+        let mut first_comparisons = Vec::new();
+        let mut second_comparisons = Vec::new();
+
+        sort_by_with(
+            &mut array.clone(),
+            |a, b| {
+                first_comparisons.push((*a, *b));
+                a.cmp(b)
+            },
+            &mut StdRng::seed_from_u64(5),
+        );
+        sort_by_with(
+            &mut array.clone(),
+            |a, b| {
+                second_comparisons.push((*a, *b));
+                a.cmp(b)
+            },
+            &mut StdRng::seed_from_u64(5),
+        );
+
+        BooleanAssert::assert_that(first_comparisons == second_comparisons).is_true();
+    }
+
+    #[test]
+    fn basics_par_sort() {
+        let array = vec![3, 2, 3, 4, 1, 6, 9, 0];
+
+        let sorted = par_sort(array, &ParallelConfig::new(4, 1));
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![0, 1, 2, 3, 3, 4, 6, 9]))
+            .in_order();
+    }
+
+    #[test]
+    fn par_sort_same_as_sequential() {
+        // equal keys with different payloads show any difference in the order of equal elements
+        let array: Vec<(i32, usize)> = (0..5000)
+            .map(|index| ((index as i32 * 7919) % 101, index))
+            .collect();
+        let compare = |a: &(i32, usize), b: &(i32, usize)| a.0.cmp(&b.0);
+        let mut sequential = array.clone();
+        sort_by_with(&mut sequential, compare, &mut StdRng::seed_from_u64(3));
+        let mut parallel = array;
+
+        par_sort_by_with(
+            &mut parallel,
+            compare,
+            &mut StdRng::seed_from_u64(3),
+            &ParallelConfig::new(8, 100),
+        );
+
+        BooleanAssert::assert_that(parallel == sequential).is_true();
+    }
 }
@@ -0,0 +1,133 @@
+use crate::sort::intro_sort::choose_pivot;
+use crate::sort::msd_radix_sort::byte_at;
+use crate::sort::three_way_quick_sort::partition;
+
+pub fn sort<S>(mut array: Vec<S>) -> Vec<S>
+where
+    S: AsRef<[u8]>,
+{
+    sort_slice(&mut array);
+
+    array
+}
+
+/// Not stable: partitioning moves strings past equal ones
+///
+/// Three-way quick sort by one byte at a time: strings with the same byte as the pivot
+/// are sorted further by the next byte, so long common prefixes are compared only once.
+pub fn sort_slice<S>(array: &mut [S])
+where
+    S: AsRef<[u8]>,
+{
+    sort_part(array, 0);
+}
+
+fn sort_part<S>(array: &mut [S], depth: usize)
+where
+    S: AsRef<[u8]>,
+{
+    if array.len() <= 1 {
+        return;
+    }
+
+    let mut compare = |first: &S, second: &S| {
+        byte_at(first.as_ref(), depth).cmp(&byte_at(second.as_ref(), depth))
+    };
+    let pivot_index = choose_pivot(array, &mut compare);
+    array.swap(0, pivot_index);
+    let (lower_at_place, greater_at_place) = partition(array, &mut compare);
+    let pivot_has_ended = byte_at(array[lower_at_place].as_ref(), depth).is_none();
+
+    let (lower_and_equal, greater) = array.split_at_mut(greater_at_place + 1);
+    let (lower, equal) = lower_and_equal.split_at_mut(lower_at_place);
+    sort_part(lower, depth);
+    if !pivot_has_ended {
+        sort_part(equal, depth + 1);
+    }
+    sort_part(greater, depth);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sort::three_way_string_quick_sort::{sort, sort_slice};
+    use easy_assert::list_assertions::ListAssert;
+    use easy_assert::{actual_vec, expected_vec};
+
+    #[test]
+    fn basics() {
+        let array = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "the",
+            "shells",
+            "she",
+            "sells",
+            "are",
+            "surely",
+            "seashells",
+        ];
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(vec![
+                "are",
+                "by",
+                "sea",
+                "seashells",
+                "seashells",
+                "sells",
+                "sells",
+                "she",
+                "she",
+                "shells",
+                "shore",
+                "surely",
+                "the",
+                "the",
+            ]))
+            .in_order();
+    }
+
+    #[test]
+    fn basics_slice() {
+        let mut array: Vec<String> = vec!["z", "bb", "b", "", "ab", "a"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        sort_slice(&mut array[1..5]);
+
+        ListAssert::assert_that(actual_vec(array))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(
+                vec!["z", "", "ab", "b", "bb", "a"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ))
+            .in_order();
+    }
+
+    #[test]
+    fn many_strings_with_shared_prefixes() {
+        let array: Vec<String> = (0..500)
+            .map(|value: u32| ((value * 7919) % 1009).to_string())
+            .collect();
+        let mut expected = array.clone();
+        expected.sort();
+
+        let sorted = sort(array);
+
+        ListAssert::assert_that(actual_vec(sorted))
+            .with_element_matcher(|a, b| a.eq(b))
+            .is_equal_to(expected_vec(expected))
+            .in_order();
+    }
+}
@@ -41,7 +41,7 @@ pub fn quick_union() {
     validate_result(union_find);
 }
 
-fn validate_result(union_find: (impl UnionFind + Sized)) {
+fn validate_result(union_find: impl UnionFind + Sized) {
     BooleanAssert::assert_that(union_find.connected(&4, &3).expect("should be Ok")).is_true();
     BooleanAssert::assert_that(union_find.connected(&4, &4).expect("should be Ok")).is_true();
     BooleanAssert::assert_that(union_find.connected(&4, &9).expect("should be Ok")).is_true();
@@ -160,17 +160,17 @@ fn validate_result(union_find: (impl UnionFind + Sized)) {
 }
 
 fn create_values() -> Vec<(usize, usize)> {
-    let mut values: Vec<(usize, usize)> = Vec::new();
-    values.push((4, 3)); //connect
-    values.push((3, 8)); //connect
-    values.push((6, 5)); //connect
-    values.push((9, 4)); //connect
-    values.push((2, 1)); //connect
-    values.push((8, 9)); //already connected
-    values.push((5, 0)); //print
-    values.push((7, 2)); //print
-    values.push((6, 1)); //print
-    values.push((1, 0)); //already connected
-    values.push((6, 7)); //already connected
-    return values;
+    vec![
+        (4, 3), //connect
+        (3, 8), //connect
+        (6, 5), //connect
+        (9, 4), //connect
+        (2, 1), //connect
+        (8, 9), //already connected
+        (5, 0), //print
+        (7, 2), //print
+        (6, 1), //print
+        (1, 0), //already connected
+        (6, 7), //already connected
+    ]
 }